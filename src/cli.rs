@@ -25,6 +25,12 @@ pub struct GlobalOpts {
 
     #[clap(long, global = true, default_value = "auto")]
     pub color: ColorMode,
+
+    /// Override discovered config settings: a JSON file path, a literal JSON
+    /// object, or comma-separated dotted `key=value` pairs (e.g.
+    /// `croc_dir=/tmp/x,scheduler.max_task_retries=3`).
+    #[clap(long, global = true)]
+    pub config: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
@@ -39,6 +45,38 @@ pub enum ColorMode {
 pub enum Command {
     Init(InitArgs),
     Prime(PrimeArgs),
+    Cache(CacheArgs),
+    Report(ReportArgs),
+    Rpc(RpcArgs),
+    Daemon(DaemonArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct RpcArgs {}
+
+#[derive(Debug, Args)]
+pub struct DaemonArgs {}
+
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// Plan to report on.
+    pub plan_id: String,
+
+    /// Emit machine-readable JSON instead of a table.
+    #[clap(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct CacheArgs {
+    #[clap(subcommand)]
+    pub command: CacheCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CacheCommand {
+    /// Drop and repopulate the SQLite cache from the JSONL logs.
+    Rebuild,
 }
 
 #[derive(Debug, Args)]
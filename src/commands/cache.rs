@@ -0,0 +1,20 @@
+use crate::cli::{CacheArgs, CacheCommand};
+use crate::config::Config;
+use crate::engine::CrocEngine;
+use anyhow::{Context, Result};
+
+pub async fn exec(args: CacheArgs, config_override: Option<String>) -> Result<()> {
+    let config = Config::from_current_dir()
+        .context("Failed to load config")?
+        .with_override(config_override.as_deref())?;
+    let engine = CrocEngine::new(config).await?;
+
+    match args.command {
+        CacheCommand::Rebuild => {
+            engine.full_sync().await?;
+            println!("Cache rebuilt from JSONL logs.");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use crate::cli::DaemonArgs;
+use crate::config::Config;
+use crate::daemon::Daemon;
+use crate::engine::CrocEngine;
+use anyhow::{Context, Result};
+
+pub async fn exec(_args: DaemonArgs, config_override: Option<String>) -> Result<()> {
+    let config = Config::from_current_dir()
+        .context("Failed to load config")?
+        .with_override(config_override.as_deref())?;
+    let poll_interval = config.daemon_poll_interval();
+    let max_concurrent_plans = config.max_concurrent_plans();
+    let engine = CrocEngine::new(config).await?;
+
+    println!("Starting croc daemon (poll interval {:?})", poll_interval);
+    Daemon::new(poll_interval, max_concurrent_plans)
+        .run(&engine)
+        .await?;
+
+    Ok(())
+}
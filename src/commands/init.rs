@@ -1,24 +1,16 @@
 use crate::cli::InitArgs;
 use crate::config::Config;
 use crate::engine::Storage;
+use crate::schemas::{Event, EventType};
 use anyhow::{Context, Result};
-use chrono::Utc;
-use serde::Serialize;
 use std::fs;
 use std::path::Path;
 use tracing::{debug, info};
 
-#[derive(Serialize)]
-struct InitEvent {
-    id: String,
-    r#type: &'static str,
-    timestamp: String,
-}
-
-pub async fn exec(args: InitArgs) -> Result<()> {
+pub async fn exec(args: InitArgs, config_override: Option<String>) -> Result<()> {
     let project_root = args.path.unwrap_or(std::env::current_dir()?);
 
-    let config = Config::new(project_root.clone());
+    let config = Config::new(project_root.clone()).with_override(config_override.as_deref())?;
 
     if config.is_initialized() {
         println!("Already initialized at {}", config.croc_dir.display());
@@ -33,42 +25,37 @@ pub async fn exec(args: InitArgs) -> Result<()> {
 
     fs::create_dir_all(config.logs_dir()).context("Failed to create logs directory")?;
 
-    let storage = Storage::new(config.clone());
-
-    create_empty_jsonl(&storage, &config.plans_file())?;
-    create_empty_jsonl(&storage, &config.tasks_file())?;
-    create_empty_jsonl(&storage, &config.context_file())?;
-    create_empty_jsonl(&storage, &config.events_file())?;
-    create_empty_jsonl(&storage, &config.reviews_file())?;
+    let storage = Storage::connect(config.clone())
+        .await
+        .context("Failed to initialize storage backend")?;
+    storage
+        .initialize()
+        .await
+        .context("Failed to prepare storage backend")?;
 
     write_gitignore(&config.gitignore_file())?;
 
-    let init_event = InitEvent {
-        id: format!("evt-{}", Utc::now().timestamp_millis()),
-        r#type: "initialized",
-        timestamp: Utc::now().to_rfc3339(),
-    };
-    storage.append_jsonl_locked(&config.events_file(), &init_event)?;
+    let init_event = Event::new(EventType::Initialized);
+    storage
+        .append_event(init_event)
+        .await
+        .context("Failed to record init event")?;
 
     check_git_repo(&project_root);
 
-    info!(path = %config.croc_dir.display(), "Initialized");
-    println!("Initialized crocodile at {}", config.croc_dir.display());
+    info!(path = %config.croc_dir.display(), backend = ?config.storage.backend, "Initialized");
+    println!(
+        "Initialized crocodile at {} ({:?} backend)",
+        config.croc_dir.display(),
+        config.storage.backend
+    );
 
     Ok(())
 }
 
-fn create_empty_jsonl(storage: &Storage, path: &Path) -> Result<()> {
-    debug!(file = %path.display(), "Creating JSONL file");
-    storage
-        .create_empty_file(path)
-        .context(format!("Failed to create {}", path.display()))?;
-    Ok(())
-}
-
 fn write_gitignore(path: &Path) -> Result<()> {
     debug!(file = %path.display(), "Writing .gitignore");
-    fs::write(path, "cache.db\nworktrees/\nlogs/\n").context("Failed to write .gitignore")?;
+    fs::write(path, "cache.db\nstorage.db*\nworktrees/\nlogs/\n").context("Failed to write .gitignore")?;
     Ok(())
 }
 
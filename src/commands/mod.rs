@@ -1,5 +1,13 @@
+pub mod cache;
+pub mod daemon;
 pub mod init;
 pub mod prime;
+pub mod report;
+pub mod rpc;
 
+pub use cache::exec as cache_exec;
+pub use daemon::exec as daemon_exec;
 pub use init::exec as init_exec;
 pub use prime::exec as prime_exec;
+pub use report::exec as report_exec;
+pub use rpc::exec as rpc_exec;
@@ -2,11 +2,28 @@ use crate::cli::PrimeArgs;
 use crate::config::Config;
 use crate::engine::CrocEngine;
 use crate::error::CrocError;
+use crate::hooks::HookEngine;
 use crate::schemas::{ContextItem, Plan, Role, Task};
 use anyhow::{Context, Result};
 use std::env;
 
-pub async fn exec(_args: PrimeArgs) -> Result<()> {
+/// Runs `prompt` through `.croc/hooks.lua`'s `pre_prime(role, ctx)`, if a
+/// hooks file is present, so users can append house conventions without
+/// forking this module.
+fn apply_pre_prime_hook(config: &Config, role: Role, ctx: serde_json::Value, prompt: String) -> Result<String> {
+    let Some(hooks) = HookEngine::load(&config.hooks_file())? else {
+        return Ok(prompt);
+    };
+
+    let role_str = serde_json::to_value(role)?
+        .as_str()
+        .unwrap_or("unknown")
+        .to_string();
+
+    Ok(hooks.pre_prime(&role_str, ctx, &prompt)?)
+}
+
+pub async fn exec(_args: PrimeArgs, config_override: Option<String>) -> Result<()> {
     let role_str = env::var("CROC_ROLE").map_err(|_| CrocError::MissingEnvVar {
         name: "CROC_ROLE".to_string(),
     })?;
@@ -17,15 +34,27 @@ pub async fn exec(_args: PrimeArgs) -> Result<()> {
         })?;
 
     match role {
-        Role::Planner => print_planner_context(),
-        Role::Foreman => print_foreman_context().await,
-        Role::Worker => print_worker_context().await,
-        Role::Reviewer => print_reviewer_context().await,
+        Role::Planner => print_planner_context(config_override.as_deref()),
+        Role::Foreman => print_foreman_context(config_override.as_deref()).await,
+        Role::Worker => print_worker_context(config_override.as_deref()).await,
+        Role::Reviewer => print_reviewer_context(config_override.as_deref()).await,
     }
 }
 
-fn print_planner_context() -> Result<()> {
-    println!(
+fn print_planner_context(config_override: Option<&str>) -> Result<()> {
+    let config = Config::from_current_dir()
+        .context("Failed to load config")?
+        .with_override(config_override)?;
+
+    let prompt = build_planner_prompt();
+    let prompt = apply_pre_prime_hook(&config, Role::Planner, serde_json::json!({}), prompt)?;
+
+    println!("{}", prompt);
+    Ok(())
+}
+
+fn build_planner_prompt() -> String {
+    format!(
         r#"# Crocodile Planner Mode
 
 You are in **planning mode**. Your role is to collaborate with the user to create a clear, actionable plan.
@@ -49,27 +78,31 @@ The plan will then be handed off to the Foreman for execution.
 - Note any files that will be touched
 - Consider edge cases and error handling
 - No code execution in this phase - planning only"#
-    );
-
-    Ok(())
+    )
 }
 
-async fn print_foreman_context() -> Result<()> {
+async fn print_foreman_context(config_override: Option<&str>) -> Result<()> {
     let plan_id = env::var("CROC_PLAN_ID").map_err(|_| CrocError::MissingEnvVar {
         name: "CROC_PLAN_ID".to_string(),
     })?;
 
-    let config = Config::from_current_dir().context("Failed to load config")?;
+    let config = Config::from_current_dir()
+        .context("Failed to load config")?
+        .with_override(config_override)?;
     let engine = CrocEngine::new(config).await?;
     let plan = engine.get_plan(&plan_id).await?;
     let tasks = engine.get_tasks_for_plan(&plan_id).await?;
     let context = engine.get_context_for_plan(&plan_id).await?;
 
-    println!("{}", build_foreman_prompt(&plan, &tasks, &context));
+    let prompt = build_foreman_prompt(&plan, &tasks, &context);
+    let ctx = serde_json::json!({ "plan_id": plan_id });
+    let prompt = apply_pre_prime_hook(engine.config(), Role::Foreman, ctx, prompt)?;
+
+    println!("{}", prompt);
     Ok(())
 }
 
-async fn print_worker_context() -> Result<()> {
+async fn print_worker_context(config_override: Option<&str>) -> Result<()> {
     let subtask_id = env::var("CROC_SUBTASK_ID").map_err(|_| CrocError::MissingEnvVar {
         name: "CROC_SUBTASK_ID".to_string(),
     })?;
@@ -78,32 +111,44 @@ async fn print_worker_context() -> Result<()> {
         name: "CROC_PLAN_ID".to_string(),
     })?;
 
-    let config = Config::from_current_dir().context("Failed to load config")?;
+    let config = Config::from_current_dir()
+        .context("Failed to load config")?
+        .with_override(config_override)?;
     let engine = CrocEngine::new(config).await?;
     let task = engine.get_task(&subtask_id).await?;
     let plan = engine.get_plan(&plan_id).await?;
     let context = engine.get_context_for_task(&subtask_id).await?;
     let plan_context = engine.get_context_for_plan(&plan_id).await?;
 
-    println!(
-        "{}",
-        build_worker_prompt(&plan, &task, &context, &plan_context)
-    );
+    let prompt = build_worker_prompt(&plan, &task, &context, &plan_context);
+    let ctx = serde_json::json!({ "plan_id": plan_id, "subtask_id": subtask_id });
+    let prompt = apply_pre_prime_hook(engine.config(), Role::Worker, ctx, prompt)?;
+
+    println!("{}", prompt);
     Ok(())
 }
 
-async fn print_reviewer_context() -> Result<()> {
+async fn print_reviewer_context(config_override: Option<&str>) -> Result<()> {
     let plan_id = env::var("CROC_PLAN_ID").map_err(|_| CrocError::MissingEnvVar {
         name: "CROC_PLAN_ID".to_string(),
     })?;
 
-    let config = Config::from_current_dir().context("Failed to load config")?;
+    let config = Config::from_current_dir()
+        .context("Failed to load config")?
+        .with_override(config_override)?;
     let engine = CrocEngine::new(config).await?;
     let plan = engine.get_plan(&plan_id).await?;
     let tasks = engine.get_tasks_for_plan(&plan_id).await?;
     let context = engine.get_context_for_plan(&plan_id).await?;
+    // Nothing currently populates `Task.worktree`, so there's no per-task
+    // git history to run the Conventional Commits check against yet.
+    let commits: Vec<String> = Vec::new();
 
-    println!("{}", build_reviewer_prompt(&plan, &tasks, &context));
+    let prompt = build_reviewer_prompt(&plan, &tasks, &context, &commits);
+    let ctx = serde_json::json!({ "plan_id": plan_id });
+    let prompt = apply_pre_prime_hook(engine.config(), Role::Reviewer, ctx, prompt)?;
+
+    println!("{}", prompt);
     Ok(())
 }
 
@@ -275,7 +320,7 @@ EXIT_READY: <true|false>
     prompt
 }
 
-fn build_reviewer_prompt(plan: &Plan, tasks: &[Task], context: &[ContextItem]) -> String {
+fn build_reviewer_prompt(plan: &Plan, tasks: &[Task], context: &[ContextItem], commits: &[String]) -> String {
     let mut prompt = format!(
         r#"# Crocodile Reviewer Mode
 
@@ -307,6 +352,8 @@ fn build_reviewer_prompt(plan: &Plan, tasks: &[Task], context: &[ContextItem]) -
         }
     }
 
+    prompt.push_str(&crate::review::build_commit_compliance_section(commits));
+
     prompt.push_str(
         r#"
 ## Your Responsibilities
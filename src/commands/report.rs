@@ -0,0 +1,22 @@
+use crate::cli::ReportArgs;
+use crate::config::Config;
+use crate::engine::report::render_table;
+use crate::engine::CrocEngine;
+use anyhow::{Context, Result};
+
+pub async fn exec(args: ReportArgs, config_override: Option<String>) -> Result<()> {
+    let config = Config::from_current_dir()
+        .context("Failed to load config")?
+        .with_override(config_override.as_deref())?;
+    let engine = CrocEngine::new(config).await?;
+
+    let sheet = engine.plan_timesheet(&args.plan_id).await?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&sheet)?);
+    } else {
+        print!("{}", render_table(&sheet));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use crate::cli::RpcArgs;
+use crate::config::Config;
+use crate::engine::CrocEngine;
+use anyhow::{Context, Result};
+use std::sync::Arc;
+
+pub async fn exec(_args: RpcArgs, config_override: Option<String>) -> Result<()> {
+    let config = Config::from_current_dir()
+        .context("Failed to load config")?
+        .with_override(config_override.as_deref())?;
+    let socket_path = config.rpc_socket_path();
+    let engine = Arc::new(CrocEngine::new(config).await?);
+
+    println!("Serving JSON-RPC on {}", socket_path.display());
+    crate::rpc::serve(engine, &socket_path).await?;
+
+    Ok(())
+}
@@ -1,14 +1,62 @@
+use crate::engine::notifier::NotifierConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub croc_dir: PathBuf,
+
+    #[serde(default)]
+    pub storage: StorageSettings,
+
+    #[serde(default)]
+    pub scheduler: SchedulerSettings,
+}
+
+/// Settings governing `Scheduler::tick`'s retry behavior. Nested under
+/// `scheduler` so a `--config` override can address it as
+/// `scheduler.max_task_retries=3`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulerSettings {
+    pub max_task_retries: u32,
+}
+
+impl Default for SchedulerSettings {
+    fn default() -> Self {
+        Self { max_task_retries: 2 }
+    }
+}
+
+/// Settings governing how the entity logs (`Plan`, `Task`, `ContextItem`,
+/// `Event`, `Review`) are persisted. Nested under `storage` so a `--config`
+/// override can address it as `storage.backend=sqlite`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageSettings {
+    #[serde(default)]
+    pub backend: StorageBackendKind,
+}
+
+/// Which `StorageBackend` `croc` persists entity logs to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackendKind {
+    /// Append-only JSONL logs with exclusive file locking. Zero external
+    /// dependencies; the default.
+    #[default]
+    Jsonl,
+    /// SQLite in WAL mode. Better suited to large histories or multiple
+    /// concurrent agents, where JSONL's coarse exclusive lock becomes a
+    /// bottleneck.
+    Sqlite,
 }
 
 impl Config {
     pub fn new(project_root: PathBuf) -> Self {
         Self {
             croc_dir: project_root.join(".croc"),
+            storage: StorageSettings::default(),
+            scheduler: SchedulerSettings::default(),
         }
     }
 
@@ -17,6 +65,26 @@ impl Config {
         Ok(Self::new(cwd))
     }
 
+    /// Applies a `--config` override on top of this config, returning the
+    /// merged result. `raw` may be a path to a JSON file, a literal JSON
+    /// object, or comma-separated dotted `key=value` pairs (e.g.
+    /// `croc_dir=/tmp/x,scheduler.max_task_retries=3`); dotted keys nest
+    /// into objects, which gives the override room to address nested
+    /// settings fields directly. Since `with_override` round-trips through
+    /// `serde_json::from_value` with no `deny_unknown_fields`, a key that
+    /// doesn't name a real field (or a typo'd path) is silently dropped
+    /// rather than rejected. A `None` override leaves `self` untouched.
+    pub fn with_override(self, raw: Option<&str>) -> anyhow::Result<Self> {
+        let Some(raw) = raw else {
+            return Ok(self);
+        };
+
+        let mut value = serde_json::to_value(&self)?;
+        deep_merge(&mut value, parse_override(raw)?);
+
+        Ok(serde_json::from_value(value)?)
+    }
+
     pub fn plans_file(&self) -> PathBuf {
         self.croc_dir.join("plans.jsonl")
     }
@@ -37,6 +105,12 @@ impl Config {
         self.croc_dir.join("reviews.jsonl")
     }
 
+    /// Database file used by the SQLite `StorageBackend` (distinct from
+    /// `engine::cache`'s own `cache.db`).
+    pub fn storage_db_path(&self) -> PathBuf {
+        self.croc_dir.join("storage.db")
+    }
+
     pub fn checkpoints_dir(&self) -> PathBuf {
         self.croc_dir.join("checkpoints")
     }
@@ -45,6 +119,51 @@ impl Config {
         self.croc_dir.join("logs")
     }
 
+    pub fn jobserver_path(&self) -> PathBuf {
+        self.croc_dir.join("jobserver")
+    }
+
+    /// User-supplied Lua hooks that customize role prompts and gate
+    /// transitions. Absent by default; see `crate::hooks`.
+    pub fn hooks_file(&self) -> PathBuf {
+        self.croc_dir.join("hooks.lua")
+    }
+
+    /// Unix socket the JSON-RPC control surface listens on (`croc rpc`).
+    pub fn rpc_socket_path(&self) -> PathBuf {
+        self.croc_dir.join("rpc.sock")
+    }
+
+    /// Maximum number of worker tmux sessions that may run concurrently
+    /// across all `croc` invocations sharing this `.croc` directory.
+    pub fn max_parallel_workers(&self) -> usize {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4)
+    }
+
+    /// Maximum number of times a failed task may be automatically re-spawned
+    /// before the scheduler marks it terminally failed.
+    pub fn max_task_retries(&self) -> u32 {
+        self.scheduler.max_task_retries
+    }
+
+    /// How often `croc daemon` polls the store for work to advance.
+    pub fn daemon_poll_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(5)
+    }
+
+    /// Maximum number of active plans `croc daemon` advances per tick.
+    pub fn max_concurrent_plans(&self) -> usize {
+        4
+    }
+
+    /// Notifier sinks, configured entirely through environment variables
+    /// for now (`CROC_NOTIFY_WEBHOOK`, `CROC_NOTIFY_SHELL_HOOK`, `CROC_NOTIFY_BELL`).
+    pub fn notifier_config(&self) -> NotifierConfig {
+        NotifierConfig::from_env()
+    }
+
     pub fn gitignore_file(&self) -> PathBuf {
         self.croc_dir.join(".gitignore")
     }
@@ -53,3 +172,167 @@ impl Config {
         self.croc_dir.exists()
     }
 }
+
+/// Parses a raw `--config` value into the `Value` patch it represents: a
+/// path to an existing JSON file, a literal JSON object, or comma-separated
+/// dotted `key=value` pairs.
+fn parse_override(raw: &str) -> anyhow::Result<Value> {
+    let trimmed = raw.trim();
+
+    if trimmed.starts_with('{') {
+        return serde_json::from_str(trimmed)
+            .map_err(|e| anyhow::anyhow!("failed to parse --config as JSON: {e}"));
+    }
+
+    let path = std::path::Path::new(trimmed);
+    if path.is_file() {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("failed to read config file {}: {e}", path.display()))?;
+        return serde_json::from_str(&contents).map_err(|e| {
+            anyhow::anyhow!("failed to parse config file {} as JSON: {e}", path.display())
+        });
+    }
+
+    parse_dotted_pairs(trimmed)
+}
+
+fn parse_dotted_pairs(spec: &str) -> anyhow::Result<Value> {
+    let mut root = Value::Object(serde_json::Map::new());
+
+    for pair in spec.split(',') {
+        let pair = pair.trim();
+        if pair.is_empty() {
+            continue;
+        }
+
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("invalid --config override '{pair}', expected key=value"))?;
+
+        set_dotted(&mut root, key.trim(), parse_scalar(value.trim()));
+    }
+
+    Ok(root)
+}
+
+fn set_dotted(root: &mut Value, dotted_key: &str, value: Value) {
+    let (head, rest) = match dotted_key.split_once('.') {
+        Some((head, rest)) => (head, Some(rest)),
+        None => (dotted_key, None),
+    };
+
+    let map = root
+        .as_object_mut()
+        .expect("override root is always a JSON object");
+
+    match rest {
+        Some(rest) => {
+            let nested = map
+                .entry(head.to_string())
+                .or_insert_with(|| Value::Object(serde_json::Map::new()));
+            if !nested.is_object() {
+                *nested = Value::Object(serde_json::Map::new());
+            }
+            set_dotted(nested, rest, value);
+        }
+        None => {
+            map.insert(head.to_string(), value);
+        }
+    }
+}
+
+fn parse_scalar(raw: &str) -> Value {
+    if let Ok(b) = raw.parse::<bool>() {
+        return Value::Bool(b);
+    }
+    if let Ok(n) = raw.parse::<i64>() {
+        return Value::Number(n.into());
+    }
+    if let Ok(f) = raw.parse::<f64>() {
+        if let Some(n) = serde_json::Number::from_f64(f) {
+            return Value::Number(n);
+        }
+    }
+    Value::String(raw.to_string())
+}
+
+/// Recursively merges `patch` into `base`: objects are merged key by key,
+/// with `patch` taking precedence; any other value type is overwritten
+/// wholesale by the corresponding `patch` value.
+fn deep_merge(base: &mut Value, patch: Value) {
+    match (base, patch) {
+        (Value::Object(base_map), Value::Object(patch_map)) => {
+            for (key, patch_value) in patch_map {
+                match base_map.get_mut(&key) {
+                    Some(base_value) => deep_merge(base_value, patch_value),
+                    None => {
+                        base_map.insert(key, patch_value);
+                    }
+                }
+            }
+        }
+        (base_slot, patch_value) => {
+            *base_slot = patch_value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dotted_pairs_nest_into_objects() {
+        let value = parse_override("nested.inner=hello,top=3").unwrap();
+        assert_eq!(value["nested"]["inner"], "hello");
+        assert_eq!(value["top"], 3);
+    }
+
+    #[test]
+    fn literal_json_object_parses_directly() {
+        let value = parse_override(r#"{"croc_dir": "/tmp/y"}"#).unwrap();
+        assert_eq!(value["croc_dir"], "/tmp/y");
+    }
+
+    #[test]
+    fn rejects_pair_without_equals() {
+        assert!(parse_override("not_a_pair").is_err());
+    }
+
+    #[test]
+    fn deep_merge_overlays_nested_keys_without_discarding_siblings() {
+        let mut base = serde_json::json!({"a": {"x": 1, "y": 2}});
+        let patch = serde_json::json!({"a": {"y": 99}});
+        deep_merge(&mut base, patch);
+        assert_eq!(base, serde_json::json!({"a": {"x": 1, "y": 99}}));
+    }
+
+    #[test]
+    fn with_override_overwrites_croc_dir() {
+        let config = Config::new(PathBuf::from("/project"));
+        let merged = config.with_override(Some("croc_dir=/tmp/override")).unwrap();
+        assert_eq!(merged.croc_dir, PathBuf::from("/tmp/override"));
+    }
+
+    #[test]
+    fn with_override_none_is_a_no_op() {
+        let config = Config::new(PathBuf::from("/project"));
+        let merged = config.clone().with_override(None).unwrap();
+        assert_eq!(merged.croc_dir, config.croc_dir);
+    }
+
+    #[test]
+    fn with_override_sets_nested_scheduler_field() {
+        let config = Config::new(PathBuf::from("/project"));
+        let merged = config.with_override(Some("scheduler.max_task_retries=5")).unwrap();
+        assert_eq!(merged.max_task_retries(), 5);
+    }
+
+    #[test]
+    fn with_override_silently_drops_keys_outside_config() {
+        let config = Config::new(PathBuf::from("/project"));
+        let merged = config.clone().with_override(Some("not_a_real_field=3")).unwrap();
+        assert_eq!(merged.croc_dir, config.croc_dir);
+        assert_eq!(merged.max_task_retries(), config.max_task_retries());
+    }
+}
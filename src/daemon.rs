@@ -0,0 +1,115 @@
+//! Autonomous orchestrator that replaces the manual "prime one role, spawn
+//! one agent" flow with a poll loop: each tick materializes any due
+//! cron-scheduled plans, advances every active plan's ready subtasks, folds
+//! in whatever the `Supervisor` observed in tmux, and hands a plan off to
+//! the Reviewer once its subtasks are all `Complete`. All progress lives in
+//! SQLite/JSONL, so a restart just resumes ticking.
+
+use crate::engine::{CrocEngine, Supervisor};
+use crate::error::{CrocError, Result};
+use crate::schemas::{Event, EventType, TaskStatus, TaskType};
+use crate::tmux::{reviewer_session_name, TmuxSession};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Polls `CrocEngine` for active plans and drives each one forward.
+pub struct Daemon {
+    poll_interval: Duration,
+    max_concurrent_plans: usize,
+    supervisor: Supervisor,
+}
+
+impl Daemon {
+    pub fn new(poll_interval: Duration, max_concurrent_plans: usize) -> Self {
+        Self {
+            poll_interval,
+            max_concurrent_plans,
+            supervisor: Supervisor::new(poll_interval),
+        }
+    }
+
+    /// Runs the poll loop forever. State is re-derived from `engine` on
+    /// every tick, so there is nothing to restore if this is restarted.
+    pub async fn run(&mut self, engine: &CrocEngine) -> Result<()> {
+        info!(
+            poll_interval_secs = self.poll_interval.as_secs(),
+            max_concurrent_plans = self.max_concurrent_plans,
+            "Starting croc daemon"
+        );
+
+        loop {
+            if let Err(err) = self.tick(engine).await {
+                warn!(error = %err, "Daemon tick failed, continuing");
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Runs a single pass: folds in tmux observations, then advances the
+    /// DAG and checks for review-readiness on every active plan (bounded
+    /// by `max_concurrent_plans`).
+    pub async fn tick(&mut self, engine: &CrocEngine) -> Result<()> {
+        self.supervisor.tick(engine).await?;
+
+        let materialized = engine.run_scheduled_plans().await?;
+        if !materialized.is_empty() {
+            info!(count = materialized.len(), "Materialized due scheduled plans");
+        }
+
+        let plans = engine.get_active_plans().await?;
+        for plan in plans.into_iter().take(self.max_concurrent_plans) {
+            match engine.spawn_ready_workers(&plan.id).await {
+                Ok(spawned) => {
+                    if !spawned.is_empty() {
+                        debug!(plan_id = %plan.id, spawned = spawned.len(), "Spawned ready workers");
+                    }
+                }
+                Err(CrocError::SchedulerExhausted { plan_id, remaining }) => {
+                    warn!(plan_id, ?remaining, "Plan's task DAG deadlocked, skipping");
+                    continue;
+                }
+                Err(other) => return Err(other),
+            }
+
+            self.maybe_request_review(engine, &plan.id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Hands `plan_id` to the Reviewer once every subtask has completed, no
+    /// review has been recorded for it, and no reviewer session is running.
+    async fn maybe_request_review(&self, engine: &CrocEngine, plan_id: &str) -> Result<()> {
+        let tasks = engine.get_tasks_for_plan(plan_id).await?;
+        let subtasks: Vec<_> = tasks
+            .iter()
+            .filter(|t| t.task_type == TaskType::Subtask)
+            .collect();
+
+        if subtasks.is_empty() || !subtasks.iter().all(|t| t.status == TaskStatus::Complete) {
+            return Ok(());
+        }
+
+        if engine.latest_review(plan_id).await?.is_some() {
+            return Ok(());
+        }
+
+        let session = TmuxSession::new(reviewer_session_name(plan_id));
+        if session.exists()? {
+            return Ok(());
+        }
+
+        info!(plan_id, "All subtasks complete, spawning reviewer");
+
+        session.spawn(
+            "croc",
+            &["prime"],
+            &[("CROC_ROLE", "reviewer"), ("CROC_PLAN_ID", plan_id)],
+        )?;
+
+        let event = Event::new(EventType::ReviewRequested).with_plan(plan_id.to_string());
+        engine.append_event(&event).await?;
+
+        Ok(())
+    }
+}
@@ -1,12 +1,183 @@
 use crate::error::{CrocError, Result};
 use crate::schemas::{
-    ContextItem, ContextType, Event, Plan, PlanStatus, Review, Task, TaskStatus, TaskType,
+    ContextItem, ContextType, Event, LogRecord, LogStream, Plan, PlanStatus, Review, Role, Task,
+    TaskStatus, TaskType,
 };
+use chrono::Utc;
 use sqlx::Row;
 use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use std::collections::HashSet;
 use std::path::Path;
+use std::time::Duration;
 use tracing::debug;
 
+/// One versioned, reversible schema step. `up`/`down` are lists of
+/// individual statements (not a single multi-statement blob) since the
+/// SQLite driver only prepares one statement per `query()` call.
+struct Migration {
+    version: u32,
+    up: &'static [&'static str],
+    down: &'static [&'static str],
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    up: &[
+        r#"
+		CREATE TABLE IF NOT EXISTS plans (
+			id TEXT PRIMARY KEY,
+			title TEXT NOT NULL,
+			description TEXT NOT NULL,
+			subtasks_preview TEXT NOT NULL,
+			considerations TEXT NOT NULL,
+			status TEXT NOT NULL,
+			approved_at TEXT,
+			created_at TEXT NOT NULL,
+			updated_at TEXT NOT NULL
+		)
+		"#,
+        r#"
+		CREATE TABLE IF NOT EXISTS tasks (
+			id TEXT PRIMARY KEY,
+			plan_id TEXT NOT NULL,
+			parent_id TEXT,
+			task_type TEXT NOT NULL,
+			title TEXT NOT NULL,
+			description TEXT,
+			status TEXT NOT NULL,
+			depends_on TEXT NOT NULL,
+			worktree TEXT,
+			assigned_worker TEXT,
+			retry_count INTEGER NOT NULL DEFAULT 0,
+			last_error TEXT,
+			created_at TEXT NOT NULL,
+			updated_at TEXT NOT NULL,
+			lease_expires_at TEXT,
+			heartbeat_at TEXT
+		)
+		"#,
+        r#"
+		CREATE TABLE IF NOT EXISTS context_items (
+			id TEXT PRIMARY KEY,
+			plan_id TEXT NOT NULL,
+			subtask_id TEXT,
+			item_type TEXT NOT NULL,
+			content TEXT NOT NULL,
+			source TEXT,
+			reasoning TEXT,
+			alternatives TEXT,
+			confidence REAL,
+			created_at TEXT NOT NULL
+		)
+		"#,
+        r#"
+		CREATE TABLE IF NOT EXISTS events (
+			id TEXT PRIMARY KEY,
+			event_type TEXT NOT NULL,
+			plan_id TEXT,
+			task_id TEXT,
+			data TEXT,
+			timestamp TEXT NOT NULL
+		)
+		"#,
+        r#"
+		CREATE TABLE IF NOT EXISTS reviews (
+			id TEXT PRIMARY KEY,
+			plan_id TEXT NOT NULL,
+			reviewer_type TEXT NOT NULL,
+			status TEXT NOT NULL,
+			notes TEXT NOT NULL,
+			created_at TEXT NOT NULL,
+			updated_at TEXT NOT NULL
+		)
+		"#,
+        r#"
+		CREATE TABLE IF NOT EXISTS sync_state (
+			file TEXT PRIMARY KEY,
+			line_count INTEGER NOT NULL
+		)
+		"#,
+        r#"
+		CREATE TABLE IF NOT EXISTS logs (
+			id TEXT PRIMARY KEY,
+			task_id TEXT NOT NULL,
+			role TEXT NOT NULL,
+			stream TEXT NOT NULL,
+			seq INTEGER NOT NULL,
+			line TEXT NOT NULL,
+			timestamp TEXT NOT NULL
+		)
+		"#,
+        "CREATE INDEX IF NOT EXISTS idx_logs_task_id ON logs(task_id, seq)",
+        "CREATE INDEX IF NOT EXISTS idx_tasks_plan_id ON tasks(plan_id)",
+        "CREATE INDEX IF NOT EXISTS idx_tasks_status ON tasks(status)",
+        "CREATE INDEX IF NOT EXISTS idx_context_plan_id ON context_items(plan_id)",
+        "CREATE INDEX IF NOT EXISTS idx_plans_status ON plans(status)",
+    ],
+    down: &[
+        "DROP INDEX IF EXISTS idx_plans_status",
+        "DROP INDEX IF EXISTS idx_context_plan_id",
+        "DROP INDEX IF EXISTS idx_tasks_status",
+        "DROP INDEX IF EXISTS idx_tasks_plan_id",
+        "DROP INDEX IF EXISTS idx_logs_task_id",
+        "DROP TABLE IF EXISTS logs",
+        "DROP TABLE IF EXISTS sync_state",
+        "DROP TABLE IF EXISTS reviews",
+        "DROP TABLE IF EXISTS events",
+        "DROP TABLE IF EXISTS context_items",
+        "DROP TABLE IF EXISTS tasks",
+        "DROP TABLE IF EXISTS plans",
+    ],
+}, Migration {
+    version: 2,
+    up: &[
+        "ALTER TABLE plans ADD COLUMN cron_schedule TEXT",
+        "ALTER TABLE plans ADD COLUMN next_run_at TEXT",
+    ],
+    down: &[
+        "ALTER TABLE plans DROP COLUMN next_run_at",
+        "ALTER TABLE plans DROP COLUMN cron_schedule",
+    ],
+}, Migration {
+    version: 3,
+    up: &[
+        r#"CREATE VIRTUAL TABLE IF NOT EXISTS context_fts USING fts5(content, reasoning, source, content='context_items', content_rowid='rowid')"#,
+        r#"CREATE TRIGGER IF NOT EXISTS context_items_ai AFTER INSERT ON context_items BEGIN
+			INSERT INTO context_fts(rowid, content, reasoning, source) VALUES (new.rowid, new.content, new.reasoning, new.source);
+		END"#,
+        r#"CREATE TRIGGER IF NOT EXISTS context_items_ad AFTER DELETE ON context_items BEGIN
+			INSERT INTO context_fts(context_fts, rowid, content, reasoning, source) VALUES('delete', old.rowid, old.content, old.reasoning, old.source);
+		END"#,
+        r#"CREATE TRIGGER IF NOT EXISTS context_items_au AFTER UPDATE ON context_items BEGIN
+			INSERT INTO context_fts(context_fts, rowid, content, reasoning, source) VALUES('delete', old.rowid, old.content, old.reasoning, old.source);
+			INSERT INTO context_fts(rowid, content, reasoning, source) VALUES (new.rowid, new.content, new.reasoning, new.source);
+		END"#,
+        "INSERT INTO context_fts(rowid, content, reasoning, source) SELECT rowid, content, reasoning, source FROM context_items",
+        r#"CREATE VIRTUAL TABLE IF NOT EXISTS plans_fts USING fts5(title, description, considerations, content='plans', content_rowid='rowid')"#,
+        r#"CREATE TRIGGER IF NOT EXISTS plans_ai AFTER INSERT ON plans BEGIN
+			INSERT INTO plans_fts(rowid, title, description, considerations) VALUES (new.rowid, new.title, new.description, new.considerations);
+		END"#,
+        r#"CREATE TRIGGER IF NOT EXISTS plans_ad AFTER DELETE ON plans BEGIN
+			INSERT INTO plans_fts(plans_fts, rowid, title, description, considerations) VALUES('delete', old.rowid, old.title, old.description, old.considerations);
+		END"#,
+        r#"CREATE TRIGGER IF NOT EXISTS plans_au AFTER UPDATE ON plans BEGIN
+			INSERT INTO plans_fts(plans_fts, rowid, title, description, considerations) VALUES('delete', old.rowid, old.title, old.description, old.considerations);
+			INSERT INTO plans_fts(rowid, title, description, considerations) VALUES (new.rowid, new.title, new.description, new.considerations);
+		END"#,
+        "INSERT INTO plans_fts(rowid, title, description, considerations) SELECT rowid, title, description, considerations FROM plans",
+    ],
+    down: &[
+        "DROP TRIGGER IF EXISTS plans_au",
+        "DROP TRIGGER IF EXISTS plans_ad",
+        "DROP TRIGGER IF EXISTS plans_ai",
+        "DROP TABLE IF EXISTS plans_fts",
+        "DROP TRIGGER IF EXISTS context_items_au",
+        "DROP TRIGGER IF EXISTS context_items_ad",
+        "DROP TRIGGER IF EXISTS context_items_ai",
+        "DROP TABLE IF EXISTS context_fts",
+    ],
+}];
+
 pub struct Cache {
     pool: SqlitePool,
 }
@@ -25,7 +196,7 @@ impl Cache {
             })?;
 
         let cache = Self { pool };
-        cache.run_migrations().await?;
+        cache.migrate_up().await?;
         cache.set_pragmas().await?;
 
         Ok(cache)
@@ -44,120 +215,89 @@ impl Cache {
         Ok(())
     }
 
-    async fn run_migrations(&self) -> Result<()> {
-        debug!("Running SQLite migrations");
+    /// Ensures `schema_migrations` exists and runs every migration with a
+    /// version greater than the current max applied one, in order, each
+    /// inside its own transaction.
+    async fn migrate_up(&self) -> Result<()> {
+        self.ensure_migrations_table().await?;
 
-        sqlx::query(
-            r#"
-			CREATE TABLE IF NOT EXISTS plans (
-				id TEXT PRIMARY KEY,
-				title TEXT NOT NULL,
-				description TEXT NOT NULL,
-				subtasks_preview TEXT NOT NULL,
-				considerations TEXT NOT NULL,
-				status TEXT NOT NULL,
-				approved_at TEXT,
-				created_at TEXT NOT NULL,
-				updated_at TEXT NOT NULL
-			)
-			"#,
-        )
-        .execute(&self.pool)
-        .await?;
+        let current = self.current_schema_version().await?;
 
-        sqlx::query(
-            r#"
-			CREATE TABLE IF NOT EXISTS tasks (
-				id TEXT PRIMARY KEY,
-				plan_id TEXT NOT NULL,
-				parent_id TEXT,
-				task_type TEXT NOT NULL,
-				title TEXT NOT NULL,
-				description TEXT,
-				status TEXT NOT NULL,
-				depends_on TEXT NOT NULL,
-				worktree TEXT,
-				assigned_worker TEXT,
-				created_at TEXT NOT NULL,
-				updated_at TEXT NOT NULL
-			)
-			"#,
-        )
-        .execute(&self.pool)
-        .await?;
+        for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+            debug!(version = migration.version, "Applying migration");
 
-        sqlx::query(
-            r#"
-			CREATE TABLE IF NOT EXISTS context_items (
-				id TEXT PRIMARY KEY,
-				plan_id TEXT NOT NULL,
-				subtask_id TEXT,
-				item_type TEXT NOT NULL,
-				content TEXT NOT NULL,
-				source TEXT,
-				reasoning TEXT,
-				alternatives TEXT,
-				confidence REAL,
-				created_at TEXT NOT NULL
-			)
-			"#,
-        )
-        .execute(&self.pool)
-        .await?;
+            let mut tx = self.pool.begin().await?;
+            for statement in migration.up {
+                sqlx::query(statement).execute(&mut *tx).await?;
+            }
+            sqlx::query("INSERT INTO schema_migrations (version, applied_at) VALUES (?, ?)")
+                .bind(migration.version as i64)
+                .bind(Utc::now().to_rfc3339())
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+        }
 
-        sqlx::query(
-            r#"
-			CREATE TABLE IF NOT EXISTS events (
-				id TEXT PRIMARY KEY,
-				event_type TEXT NOT NULL,
-				plan_id TEXT,
-				task_id TEXT,
-				data TEXT,
-				timestamp TEXT NOT NULL
-			)
-			"#,
-        )
-        .execute(&self.pool)
-        .await?;
+        Ok(())
+    }
+
+    /// Rolls the schema back to `target_version` by running each applied
+    /// migration's `down` SQL in reverse order.
+    pub async fn migrate_down(&self, target_version: u32) -> Result<()> {
+        self.ensure_migrations_table().await?;
 
+        let current = self.current_schema_version().await?;
+
+        for migration in MIGRATIONS
+            .iter()
+            .filter(|m| m.version > target_version && m.version <= current)
+            .rev()
+        {
+            debug!(version = migration.version, "Rolling back migration");
+
+            let mut tx = self.pool.begin().await?;
+            for statement in migration.down {
+                sqlx::query(statement).execute(&mut *tx).await?;
+            }
+            sqlx::query("DELETE FROM schema_migrations WHERE version = ?")
+                .bind(migration.version as i64)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn ensure_migrations_table(&self) -> Result<()> {
         sqlx::query(
             r#"
-			CREATE TABLE IF NOT EXISTS reviews (
-				id TEXT PRIMARY KEY,
-				plan_id TEXT NOT NULL,
-				reviewer_type TEXT NOT NULL,
-				status TEXT NOT NULL,
-				notes TEXT NOT NULL,
-				created_at TEXT NOT NULL,
-				updated_at TEXT NOT NULL
+			CREATE TABLE IF NOT EXISTS schema_migrations (
+				version INTEGER PRIMARY KEY,
+				applied_at TEXT NOT NULL
 			)
 			"#,
         )
         .execute(&self.pool)
         .await?;
 
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_tasks_plan_id ON tasks(plan_id)")
-            .execute(&self.pool)
-            .await?;
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_tasks_status ON tasks(status)")
-            .execute(&self.pool)
-            .await?;
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_context_plan_id ON context_items(plan_id)")
-            .execute(&self.pool)
-            .await?;
-        sqlx::query("CREATE INDEX IF NOT EXISTS idx_plans_status ON plans(status)")
-            .execute(&self.pool)
-            .await?;
-
         Ok(())
     }
 
+    async fn current_schema_version(&self) -> Result<u32> {
+        let row = sqlx::query("SELECT COALESCE(MAX(version), 0) AS version FROM schema_migrations")
+            .fetch_one(&self.pool)
+            .await?;
+        let version: i64 = row.get("version");
+        Ok(version as u32)
+    }
+
     pub async fn upsert_plan(&self, plan: &Plan) -> Result<()> {
         sqlx::query(
 			r#"
-			INSERT OR REPLACE INTO plans 
-			(id, title, description, subtasks_preview, considerations, status, approved_at, created_at, updated_at)
-			VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+			INSERT OR REPLACE INTO plans
+			(id, title, description, subtasks_preview, considerations, status, approved_at, cron_schedule, next_run_at, created_at, updated_at)
+			VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
 			"#,
 		)
 		.bind(&plan.id)
@@ -167,6 +307,8 @@ impl Cache {
 		.bind(serde_json::to_string(&plan.considerations)?)
 		.bind(serde_json::to_string(&plan.status)?)
 		.bind(plan.approved_at.map(|t| t.to_rfc3339()))
+		.bind(&plan.cron_schedule)
+		.bind(plan.next_run_at.map(|t| t.to_rfc3339()))
 		.bind(plan.created_at.to_rfc3339())
 		.bind(plan.updated_at.to_rfc3339())
 		.execute(&self.pool)
@@ -179,8 +321,8 @@ impl Cache {
         sqlx::query(
 			r#"
 			INSERT OR REPLACE INTO tasks 
-			(id, plan_id, parent_id, task_type, title, description, status, depends_on, worktree, assigned_worker, created_at, updated_at)
-			VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+			(id, plan_id, parent_id, task_type, title, description, status, depends_on, worktree, assigned_worker, retry_count, last_error, created_at, updated_at)
+			VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
 			"#,
 		)
 		.bind(&task.id)
@@ -193,6 +335,8 @@ impl Cache {
 		.bind(serde_json::to_string(&task.depends_on)?)
 		.bind(&task.worktree)
 		.bind(&task.assigned_worker)
+		.bind(task.retry_count as i64)
+		.bind(task.last_error.as_ref().map(|v| v.to_string()))
 		.bind(task.created_at.to_rfc3339())
 		.bind(task.updated_at.to_rfc3339())
 		.execute(&self.pool)
@@ -268,7 +412,7 @@ impl Cache {
 
     pub async fn get_plan(&self, id: &str) -> Result<Option<Plan>> {
         let row = sqlx::query(
-			"SELECT id, title, description, subtasks_preview, considerations, status, approved_at, created_at, updated_at FROM plans WHERE id = ?",
+			"SELECT id, title, description, subtasks_preview, considerations, status, approved_at, cron_schedule, next_run_at, created_at, updated_at FROM plans WHERE id = ?",
 		)
 		.bind(id)
 		.fetch_optional(&self.pool)
@@ -282,7 +426,7 @@ impl Cache {
 
     pub async fn get_task(&self, id: &str) -> Result<Option<Task>> {
         let row = sqlx::query(
-			"SELECT id, plan_id, parent_id, task_type, title, description, status, depends_on, worktree, assigned_worker, created_at, updated_at FROM tasks WHERE id = ?",
+			"SELECT id, plan_id, parent_id, task_type, title, description, status, depends_on, worktree, assigned_worker, retry_count, last_error, created_at, updated_at FROM tasks WHERE id = ?",
 		)
 		.bind(id)
 		.fetch_optional(&self.pool)
@@ -296,7 +440,7 @@ impl Cache {
 
     pub async fn get_tasks_for_plan(&self, plan_id: &str) -> Result<Vec<Task>> {
         let rows = sqlx::query(
-			"SELECT id, plan_id, parent_id, task_type, title, description, status, depends_on, worktree, assigned_worker, created_at, updated_at FROM tasks WHERE plan_id = ? ORDER BY created_at",
+			"SELECT id, plan_id, parent_id, task_type, title, description, status, depends_on, worktree, assigned_worker, retry_count, last_error, created_at, updated_at FROM tasks WHERE plan_id = ? ORDER BY created_at",
 		)
 		.bind(plan_id)
 		.fetch_all(&self.pool)
@@ -329,7 +473,7 @@ impl Cache {
 
     pub async fn get_active_plans(&self) -> Result<Vec<Plan>> {
         let rows = sqlx::query(
-			r#"SELECT id, title, description, subtasks_preview, considerations, status, approved_at, created_at, updated_at FROM plans WHERE status IN ('"approved"', '"running"') ORDER BY created_at DESC"#,
+			r#"SELECT id, title, description, subtasks_preview, considerations, status, approved_at, cron_schedule, next_run_at, created_at, updated_at FROM plans WHERE status IN ('"approved"', '"running"') ORDER BY created_at DESC"#,
 		)
 		.fetch_all(&self.pool)
 		.await?;
@@ -339,7 +483,7 @@ impl Cache {
 
     pub async fn get_all_plans(&self) -> Result<Vec<Plan>> {
         let rows = sqlx::query(
-			"SELECT id, title, description, subtasks_preview, considerations, status, approved_at, created_at, updated_at FROM plans ORDER BY created_at DESC",
+			"SELECT id, title, description, subtasks_preview, considerations, status, approved_at, cron_schedule, next_run_at, created_at, updated_at FROM plans ORDER BY created_at DESC",
 		)
 		.fetch_all(&self.pool)
 		.await?;
@@ -347,6 +491,77 @@ impl Cache {
         rows.iter().map(|r| self.row_to_plan(r)).collect()
     }
 
+    /// Scheduled plan templates (`cron_schedule` set) whose `next_run_at`
+    /// has passed, for `CrocEngine::run_scheduled_plans` to materialize.
+    pub async fn get_due_scheduled_plans(&self, now: chrono::DateTime<Utc>) -> Result<Vec<Plan>> {
+        let rows = sqlx::query(
+			r#"SELECT id, title, description, subtasks_preview, considerations, status, approved_at, cron_schedule, next_run_at, created_at, updated_at FROM plans WHERE cron_schedule IS NOT NULL AND next_run_at IS NOT NULL AND next_run_at <= ? ORDER BY next_run_at"#,
+		)
+		.bind(now.to_rfc3339())
+		.fetch_all(&self.pool)
+		.await?;
+
+        rows.iter().map(|r| self.row_to_plan(r)).collect()
+    }
+
+    /// Relevance-ranked search over context item `content`/`reasoning`/`source`,
+    /// optionally scoped to a single plan. Lower `bm25` scores rank better, so
+    /// results come back best-match-first.
+    pub async fn search_context(
+        &self,
+        query: &str,
+        plan_id: Option<&str>,
+    ) -> Result<Vec<(ContextItem, f64)>> {
+        let rows = sqlx::query(
+            r#"
+			SELECT context_items.id, context_items.plan_id, context_items.subtask_id, context_items.item_type,
+				context_items.content, context_items.source, context_items.reasoning, context_items.alternatives,
+				context_items.confidence, context_items.created_at, bm25(context_fts) AS rank
+			FROM context_fts
+			JOIN context_items ON context_items.rowid = context_fts.rowid
+			WHERE context_fts MATCH ? AND (? IS NULL OR context_items.plan_id = ?)
+			ORDER BY rank
+			"#,
+        )
+        .bind(query)
+        .bind(plan_id)
+        .bind(plan_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|r| {
+                let rank: f64 = r.get("rank");
+                Ok((self.row_to_context(r)?, rank))
+            })
+            .collect()
+    }
+
+    /// Relevance-ranked search over plan `title`/`description`/`considerations`.
+    pub async fn search_plans(&self, query: &str) -> Result<Vec<(Plan, f64)>> {
+        let rows = sqlx::query(
+            r#"
+			SELECT plans.id, plans.title, plans.description, plans.subtasks_preview, plans.considerations,
+				plans.status, plans.approved_at, plans.cron_schedule, plans.next_run_at, plans.created_at, plans.updated_at,
+				bm25(plans_fts) AS rank
+			FROM plans_fts
+			JOIN plans ON plans.rowid = plans_fts.rowid
+			WHERE plans_fts MATCH ?
+			ORDER BY rank
+			"#,
+        )
+        .bind(query)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|r| {
+                let rank: f64 = r.get("rank");
+                Ok((self.row_to_plan(r)?, rank))
+            })
+            .collect()
+    }
+
     pub async fn clear_all(&self) -> Result<()> {
         sqlx::query("DELETE FROM plans").execute(&self.pool).await?;
         sqlx::query("DELETE FROM tasks").execute(&self.pool).await?;
@@ -359,9 +574,317 @@ impl Cache {
         sqlx::query("DELETE FROM reviews")
             .execute(&self.pool)
             .await?;
+        sqlx::query("DELETE FROM sync_state")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Appends a single log line for `task_id`, assigning it the next
+    /// sequence number so readers can resume with `logs_since`.
+    pub async fn insert_log(&self, record: &LogRecord) -> Result<()> {
+        sqlx::query(
+			r#"
+			INSERT OR REPLACE INTO logs
+			(id, task_id, role, stream, seq, line, timestamp)
+			VALUES (?, ?, ?, ?, ?, ?, ?)
+			"#,
+		)
+		.bind(&record.id)
+		.bind(&record.task_id)
+		.bind(serde_json::to_string(&record.role)?)
+		.bind(serde_json::to_string(&record.stream)?)
+		.bind(record.seq as i64)
+		.bind(&record.line)
+		.bind(record.timestamp.to_rfc3339())
+		.execute(&self.pool)
+		.await?;
+
         Ok(())
     }
 
+    /// The next sequence number to use for `task_id` (one past the highest
+    /// seen, starting at 1, so `get_logs_since(task_id, 0)` returns every line).
+    pub async fn next_log_seq(&self, task_id: &str) -> Result<u64> {
+        let row = sqlx::query("SELECT MAX(seq) AS max_seq FROM logs WHERE task_id = ?")
+            .bind(task_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let max_seq: Option<i64> = row.get("max_seq");
+        Ok(max_seq.map(|s| s as u64 + 1).unwrap_or(1))
+    }
+
+    /// All log records for `task_id` with `seq > after_seq`, in order.
+    pub async fn get_logs_since(&self, task_id: &str, after_seq: u64) -> Result<Vec<LogRecord>> {
+        let rows = sqlx::query(
+            "SELECT id, task_id, role, stream, seq, line, timestamp FROM logs WHERE task_id = ? AND seq > ? ORDER BY seq",
+        )
+        .bind(task_id)
+        .bind(after_seq as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(|r| self.row_to_log(r)).collect()
+    }
+
+    /// Returns the number of lines of `file` already folded into the cache.
+    pub async fn get_sync_watermark(&self, file: &str) -> Result<usize> {
+        let row = sqlx::query("SELECT line_count FROM sync_state WHERE file = ?")
+            .bind(file)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(match row {
+            Some(row) => {
+                let count: i64 = row.get("line_count");
+                count as usize
+            }
+            None => 0,
+        })
+    }
+
+    /// Records how many lines of `file` have been folded into the cache so
+    /// far, so the next reconcile only replays what's new.
+    pub async fn set_sync_watermark(&self, file: &str, line_count: usize) -> Result<()> {
+        sqlx::query("INSERT OR REPLACE INTO sync_state (file, line_count) VALUES (?, ?)")
+            .bind(file)
+            .bind(line_count as i64)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Pending tasks in `plan_id` whose `depends_on` are all `Complete`.
+    pub async fn ready_tasks(&self, plan_id: &str) -> Result<Vec<Task>> {
+        let tasks = self.get_tasks_for_plan(plan_id).await?;
+        let complete: std::collections::HashSet<&str> = tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Complete)
+            .map(|t| t.id.as_str())
+            .collect();
+
+        Ok(tasks
+            .into_iter()
+            .filter(|t| {
+                t.status == TaskStatus::Pending
+                    && t.depends_on.iter().all(|d| complete.contains(d.as_str()))
+            })
+            .collect())
+    }
+
+    /// Atomically claims the oldest pending task whose dependencies are all
+    /// `complete`, so concurrent workers never grab the same row. Retries
+    /// the select/update under a fresh `BEGIN IMMEDIATE` transaction if
+    /// another worker wins the race for the chosen candidate.
+    pub async fn claim_next_runnable_task(&self, worker_id: &str, lease: Duration) -> Result<Option<Task>> {
+        loop {
+            let mut conn = self.pool.acquire().await?;
+            sqlx::query("BEGIN IMMEDIATE").execute(&mut *conn).await?;
+
+            let rows = sqlx::query(
+				"SELECT id, plan_id, parent_id, task_type, title, description, status, depends_on, worktree, assigned_worker, retry_count, last_error, created_at, updated_at FROM tasks ORDER BY created_at",
+			)
+			.fetch_all(&mut *conn)
+			.await?;
+
+            let all_tasks = rows
+                .iter()
+                .map(|row| self.row_to_task(row))
+                .collect::<Result<Vec<Task>>>()?;
+
+            let complete: HashSet<&str> = all_tasks
+                .iter()
+                .filter(|t| t.status == TaskStatus::Complete)
+                .map(|t| t.id.as_str())
+                .collect();
+
+            let candidate = all_tasks.into_iter().find(|t| {
+                t.status == TaskStatus::Pending && t.depends_on.iter().all(|d| complete.contains(d.as_str()))
+            });
+
+            let Some(task) = candidate else {
+                sqlx::query("ROLLBACK").execute(&mut *conn).await?;
+                return Ok(None);
+            };
+
+            let now = Utc::now();
+            let lease_expires_at =
+                now + chrono::Duration::from_std(lease).unwrap_or_else(|_| chrono::Duration::seconds(60));
+
+            let result = sqlx::query(
+				r#"UPDATE tasks SET status = '"running"', assigned_worker = ?, heartbeat_at = ?, lease_expires_at = ?, updated_at = ? WHERE id = ? AND status = '"pending"'"#,
+			)
+			.bind(worker_id)
+			.bind(now.to_rfc3339())
+			.bind(lease_expires_at.to_rfc3339())
+			.bind(now.to_rfc3339())
+			.bind(&task.id)
+			.execute(&mut *conn)
+			.await?;
+
+            if result.rows_affected() != 1 {
+                sqlx::query("ROLLBACK").execute(&mut *conn).await?;
+                debug!(task_id = %task.id, "Lost claim race, retrying");
+                continue;
+            }
+
+            sqlx::query("COMMIT").execute(&mut *conn).await?;
+
+            let mut claimed = task;
+            claimed.status = TaskStatus::Running;
+            claimed.assigned_worker = Some(worker_id.to_string());
+            claimed.updated_at = now;
+            return Ok(Some(claimed));
+        }
+    }
+
+    /// Pushes `task_id`'s lease forward; called periodically by the worker
+    /// holding it so `reclaim_expired_tasks` doesn't steal it mid-run.
+    pub async fn heartbeat_task(&self, task_id: &str, worker_id: &str) -> Result<()> {
+        let now = Utc::now();
+        let row = sqlx::query(
+            "SELECT heartbeat_at, lease_expires_at FROM tasks WHERE id = ? AND assigned_worker = ?",
+        )
+        .bind(task_id)
+        .bind(worker_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        // Extends by the lease length granted at claim time, which
+        // `lease_expires_at - heartbeat_at` keeps across heartbeats.
+        let parse = |s: Option<String>| s.and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok());
+        let new_expiry = row
+            .and_then(|row| Some(parse(row.get("lease_expires_at"))? - parse(row.get("heartbeat_at"))?))
+            .map(|lease| (now + lease).to_rfc3339());
+
+        sqlx::query(
+            "UPDATE tasks SET heartbeat_at = ?, lease_expires_at = COALESCE(?, lease_expires_at) WHERE id = ? AND assigned_worker = ?",
+        )
+        .bind(now.to_rfc3339())
+        .bind(new_expiry)
+        .bind(task_id)
+        .bind(worker_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Resets every `running` task whose lease has expired back to
+    /// `pending` and clears its assignment, returning the reclaimed ids so
+    /// the caller can emit an event per task.
+    pub async fn reclaim_expired_tasks(&self) -> Result<Vec<String>> {
+        let now = Utc::now().to_rfc3339();
+
+        let rows = sqlx::query(
+			r#"SELECT id FROM tasks WHERE status = '"running"' AND lease_expires_at IS NOT NULL AND lease_expires_at < ?"#,
+		)
+		.bind(&now)
+		.fetch_all(&self.pool)
+		.await?;
+
+        let ids: Vec<String> = rows.iter().map(|row| row.get("id")).collect();
+
+        for id in &ids {
+            sqlx::query(
+				r#"UPDATE tasks SET status = '"pending"', assigned_worker = NULL, lease_expires_at = NULL, heartbeat_at = NULL, updated_at = ? WHERE id = ?"#,
+			)
+			.bind(&now)
+			.bind(id)
+			.execute(&self.pool)
+			.await?;
+        }
+
+        Ok(ids)
+    }
+
+    /// Events recorded strictly after `timestamp`, in timestamp order.
+    pub async fn get_events_since(&self, timestamp: chrono::DateTime<Utc>) -> Result<Vec<Event>> {
+        let rows = sqlx::query(
+            "SELECT id, event_type, plan_id, task_id, data, timestamp FROM events WHERE timestamp > ? ORDER BY timestamp",
+        )
+        .bind(timestamp.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(|r| self.row_to_event(r)).collect()
+    }
+
+    fn row_to_event(&self, row: &sqlx::sqlite::SqliteRow) -> Result<Event> {
+        let event_type_str: String = row.get("event_type");
+        let event_type = serde_json::from_str(&event_type_str)?;
+
+        let data_str: Option<String> = row.get("data");
+        let data = data_str.map(|s| serde_json::from_str(&s)).transpose()?;
+
+        let timestamp: String = row.get("timestamp");
+        let timestamp = chrono::DateTime::parse_from_rfc3339(&timestamp)
+            .map_err(|e| CrocError::Cache {
+                message: format!("Failed to parse timestamp: {}", e),
+            })?
+            .with_timezone(&chrono::Utc);
+
+        Ok(Event {
+            id: row.get("id"),
+            event_type,
+            plan_id: row.get("plan_id"),
+            task_id: row.get("task_id"),
+            data,
+            timestamp,
+        })
+    }
+
+    /// The most recently created/updated review recorded for `plan_id`, if any.
+    pub async fn latest_review(&self, plan_id: &str) -> Result<Option<Review>> {
+        let row = sqlx::query(
+            "SELECT id, plan_id, reviewer_type, status, notes, created_at, updated_at FROM reviews WHERE plan_id = ? ORDER BY updated_at DESC LIMIT 1",
+        )
+        .bind(plan_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        match row {
+            Some(row) => Ok(Some(self.row_to_review(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn row_to_review(&self, row: &sqlx::sqlite::SqliteRow) -> Result<Review> {
+        let reviewer_type_str: String = row.get("reviewer_type");
+        let reviewer_type = serde_json::from_str(&reviewer_type_str)?;
+
+        let status_str: String = row.get("status");
+        let status = serde_json::from_str(&status_str)?;
+
+        let notes_str: String = row.get("notes");
+        let notes: Vec<String> = serde_json::from_str(&notes_str)?;
+
+        let created_at: String = row.get("created_at");
+        let created_at = chrono::DateTime::parse_from_rfc3339(&created_at)
+            .map_err(|e| CrocError::Cache {
+                message: format!("Failed to parse created_at: {}", e),
+            })?
+            .with_timezone(&chrono::Utc);
+
+        let updated_at: String = row.get("updated_at");
+        let updated_at = chrono::DateTime::parse_from_rfc3339(&updated_at)
+            .map_err(|e| CrocError::Cache {
+                message: format!("Failed to parse updated_at: {}", e),
+            })?
+            .with_timezone(&chrono::Utc);
+
+        Ok(Review {
+            id: row.get("id"),
+            plan_id: row.get("plan_id"),
+            reviewer_type,
+            status,
+            notes,
+            created_at,
+            updated_at,
+        })
+    }
+
     fn row_to_plan(&self, row: &sqlx::sqlite::SqliteRow) -> Result<Plan> {
         let status_str: String = row.get("status");
         let status: PlanStatus = serde_json::from_str(&status_str)?;
@@ -381,6 +904,15 @@ impl Cache {
             })?
             .map(|dt| dt.with_timezone(&chrono::Utc));
 
+        let next_run_at: Option<String> = row.get("next_run_at");
+        let next_run_at = next_run_at
+            .map(|s| chrono::DateTime::parse_from_rfc3339(&s))
+            .transpose()
+            .map_err(|e| CrocError::Cache {
+                message: format!("Failed to parse next_run_at: {}", e),
+            })?
+            .map(|dt| dt.with_timezone(&chrono::Utc));
+
         let created_at: String = row.get("created_at");
         let created_at = chrono::DateTime::parse_from_rfc3339(&created_at)
             .map_err(|e| CrocError::Cache {
@@ -403,6 +935,8 @@ impl Cache {
             considerations,
             status,
             approved_at,
+            cron_schedule: row.get("cron_schedule"),
+            next_run_at,
             created_at,
             updated_at,
         })
@@ -418,6 +952,12 @@ impl Cache {
         let depends_on_str: String = row.get("depends_on");
         let depends_on: Vec<String> = serde_json::from_str(&depends_on_str)?;
 
+        let retry_count: i64 = row.get("retry_count");
+        let last_error_str: Option<String> = row.get("last_error");
+        let last_error = last_error_str
+            .map(|s| serde_json::from_str(&s))
+            .transpose()?;
+
         let created_at: String = row.get("created_at");
         let created_at = chrono::DateTime::parse_from_rfc3339(&created_at)
             .map_err(|e| CrocError::Cache {
@@ -443,6 +983,8 @@ impl Cache {
             depends_on,
             worktree: row.get("worktree"),
             assigned_worker: row.get("assigned_worker"),
+            retry_count: retry_count as u32,
+            last_error,
             created_at,
             updated_at,
         })
@@ -477,4 +1019,31 @@ impl Cache {
             created_at,
         })
     }
+
+    fn row_to_log(&self, row: &sqlx::sqlite::SqliteRow) -> Result<LogRecord> {
+        let role_str: String = row.get("role");
+        let role: Role = serde_json::from_str(&role_str)?;
+
+        let stream_str: String = row.get("stream");
+        let stream: LogStream = serde_json::from_str(&stream_str)?;
+
+        let seq: i64 = row.get("seq");
+
+        let timestamp: String = row.get("timestamp");
+        let timestamp = chrono::DateTime::parse_from_rfc3339(&timestamp)
+            .map_err(|e| CrocError::Cache {
+                message: format!("Failed to parse timestamp: {}", e),
+            })?
+            .with_timezone(&chrono::Utc);
+
+        Ok(LogRecord {
+            id: row.get("id"),
+            task_id: row.get("task_id"),
+            role,
+            stream,
+            seq: seq as u64,
+            line: row.get("line"),
+            timestamp,
+        })
+    }
 }
@@ -1,14 +1,28 @@
 use crate::config::Config;
 use crate::engine::cache::Cache;
+use crate::engine::cron::CronSchedule;
+use crate::engine::report::{self, PlanTimesheet};
+use crate::engine::log_sink::LogSink;
+use crate::engine::notifier::Notifier;
+use crate::engine::process_map::ProcessMap;
+use crate::engine::scheduler::Scheduler;
 use crate::engine::storage::Storage;
 use crate::error::{CrocError, Result};
-use crate::schemas::{ContextItem, Event, Plan, Review, Task};
+use crate::schemas::{
+    ContextItem, Event, EventType, LogRecord, LogStream, Plan, PlanStatus, Review, Role, Task,
+    TaskStatus,
+};
+use crate::status::{self, WorkerStatus};
+use chrono::Utc;
+use serde::{de::DeserializeOwned, Serialize};
+use std::collections::HashMap;
 use tracing::{debug, info};
 
 pub struct CrocEngine {
     storage: Storage,
     cache: Cache,
     config: Config,
+    process_map: ProcessMap<String, serde_json::Value>,
 }
 
 impl CrocEngine {
@@ -24,12 +38,13 @@ impl CrocEngine {
 
         let cache_path = config.croc_dir.join("cache.db");
         let cache = Cache::new(&cache_path).await?;
-        let storage = Storage::new(config.clone());
+        let storage = Storage::connect(config.clone()).await?;
 
         let engine = Self {
             storage,
             cache,
             config,
+            process_map: ProcessMap::new(),
         };
 
         engine.ensure_cache_synced().await?;
@@ -40,70 +55,263 @@ impl CrocEngine {
     async fn ensure_cache_synced(&self) -> Result<()> {
         let plans_in_cache = self.cache.get_all_plans().await?;
         if plans_in_cache.is_empty() {
-            let plans_in_storage = self.storage.read_plans()?;
+            let plans_in_storage = self.storage.read_plans().await?;
             if !plans_in_storage.is_empty() {
                 debug!("Cache empty but storage has data, syncing...");
                 self.full_sync().await?;
+                return Ok(());
             }
         }
-        Ok(())
+
+        self.reconcile().await
     }
 
+    /// Drops and repopulates the cache from scratch; the path used by
+    /// `croc cache rebuild`.
     pub async fn full_sync(&self) -> Result<()> {
         info!("Running full sync from JSONL to SQLite cache");
 
         self.cache.clear_all().await?;
 
-        for plan in self.storage.read_plans()? {
+        for plan in self.storage.read_plans().await? {
             self.cache.upsert_plan(&plan).await?;
         }
 
-        for task in self.storage.read_tasks()? {
+        for task in self.storage.read_tasks().await? {
             self.cache.upsert_task(&task).await?;
         }
 
-        for context in self.storage.read_context()? {
+        for context in self.storage.read_context().await? {
             self.cache.upsert_context(&context).await?;
         }
 
-        for event in self.storage.read_events()? {
+        for event in self.storage.read_events().await? {
             self.cache.upsert_event(&event).await?;
         }
 
-        for review in self.storage.read_reviews()? {
+        for review in self.storage.read_reviews().await? {
             self.cache.upsert_review(&review).await?;
         }
 
+        self.save_watermarks().await?;
+
         info!("Full sync complete");
         Ok(())
     }
 
+    /// Replays only the records appended since the last recorded watermark
+    /// for each entity, so a warm cache starts in O(new records) instead of
+    /// O(entire history) regardless of which `StorageBackend` is active.
+    pub async fn reconcile(&self) -> Result<()> {
+        debug!("Reconciling cache from storage watermarks");
+
+        let watermark = self.cache.get_sync_watermark("plans").await?;
+        for plan in self.storage.read_plans_since(watermark).await? {
+            self.cache.upsert_plan(&plan).await?;
+        }
+
+        let watermark = self.cache.get_sync_watermark("tasks").await?;
+        for task in self.storage.read_tasks_since(watermark).await? {
+            self.cache.upsert_task(&task).await?;
+        }
+
+        let watermark = self.cache.get_sync_watermark("context").await?;
+        for context in self.storage.read_context_since(watermark).await? {
+            self.cache.upsert_context(&context).await?;
+        }
+
+        let watermark = self.cache.get_sync_watermark("events").await?;
+        for event in self.storage.read_events_since(watermark).await? {
+            self.cache.upsert_event(&event).await?;
+        }
+
+        let watermark = self.cache.get_sync_watermark("reviews").await?;
+        for review in self.storage.read_reviews_since(watermark).await? {
+            self.cache.upsert_review(&review).await?;
+        }
+
+        self.save_watermarks().await?;
+
+        Ok(())
+    }
+
+    /// Rebuilds the cache by folding the ordered event log forward instead
+    /// of re-reading every JSONL collection wholesale. Each call replays the
+    /// whole event log from the start and clears the cache first; it does
+    /// not catch up incrementally from `Cache::get_events_since`. Falls back to `full_sync` whenever the log can't
+    /// fully reconstruct an entity on its own — today that's any event
+    /// whose entity was never snapshotted via `Event::with_data` (most task
+    /// and review events, since there is no `TaskCreated`/`ContextAppended`
+    /// event type yet to seed a projection from nothing).
+    pub async fn rebuild_from_events(&self) -> Result<()> {
+        info!("Rebuilding cache from event log");
+
+        let events = self.storage.read_events().await?;
+
+        let mut plans: HashMap<String, Plan> = HashMap::new();
+        let mut tasks: HashMap<String, Task> = HashMap::new();
+        let mut reviews: HashMap<String, Review> = HashMap::new();
+        let mut incomplete = false;
+
+        for event in &events {
+            match event.event_type {
+                EventType::PlanCreated => match Self::snapshot::<Plan>(event) {
+                    Some(plan) => {
+                        plans.insert(plan.id.clone(), plan);
+                    }
+                    None => incomplete = true,
+                },
+                EventType::PlanApproved | EventType::PlanComplete | EventType::PlanCancelled => {
+                    if let Some(plan) = Self::snapshot::<Plan>(event) {
+                        plans.insert(plan.id.clone(), plan);
+                    } else {
+                        match event.plan_id.as_deref().and_then(|id| plans.get_mut(id)) {
+                            Some(plan) => {
+                                plan.status = match event.event_type {
+                                    EventType::PlanApproved => PlanStatus::Approved,
+                                    EventType::PlanComplete => PlanStatus::Complete,
+                                    EventType::PlanCancelled => PlanStatus::Cancelled,
+                                    _ => unreachable!(),
+                                };
+                                if event.event_type == EventType::PlanApproved {
+                                    plan.approved_at = Some(event.timestamp);
+                                }
+                                plan.updated_at = event.timestamp;
+                            }
+                            None => incomplete = true,
+                        }
+                    }
+                }
+                EventType::WorkerSpawned
+                | EventType::WorkerProgress
+                | EventType::WorkerComplete
+                | EventType::WorkerFailed
+                | EventType::WorkerBlocked => {
+                    if let Some(task) = Self::snapshot::<Task>(event) {
+                        tasks.insert(task.id.clone(), task);
+                    } else {
+                        match event.task_id.as_deref().and_then(|id| tasks.get_mut(id)) {
+                            Some(task) => {
+                                if let EventType::WorkerSpawned
+                                | EventType::WorkerComplete
+                                | EventType::WorkerFailed
+                                | EventType::WorkerBlocked = event.event_type
+                                {
+                                    task.status = match event.event_type {
+                                        EventType::WorkerSpawned => TaskStatus::Running,
+                                        EventType::WorkerComplete => TaskStatus::Complete,
+                                        EventType::WorkerFailed => TaskStatus::Failed,
+                                        EventType::WorkerBlocked => TaskStatus::Blocked,
+                                        _ => unreachable!(),
+                                    };
+                                }
+                                task.updated_at = event.timestamp;
+                            }
+                            None => incomplete = true,
+                        }
+                    }
+                }
+                EventType::ReviewApproved | EventType::ReviewChangesRequested => {
+                    if let Some(review) = Self::snapshot::<Review>(event) {
+                        reviews.insert(review.id.clone(), review);
+                    } else {
+                        incomplete = true;
+                    }
+                }
+                _ => {}
+            }
+
+            if incomplete {
+                break;
+            }
+        }
+
+        if incomplete {
+            debug!("Event log incomplete, falling back to full JSONL sync");
+            return self.full_sync().await;
+        }
+
+        self.cache.clear_all().await?;
+
+        for plan in plans.values() {
+            self.cache.upsert_plan(plan).await?;
+        }
+        for task in tasks.values() {
+            self.cache.upsert_task(task).await?;
+        }
+        for context in self.storage.read_context().await? {
+            self.cache.upsert_context(&context).await?;
+        }
+        for event in &events {
+            self.cache.upsert_event(event).await?;
+        }
+        for review in reviews.values() {
+            self.cache.upsert_review(review).await?;
+        }
+
+        self.save_watermarks().await?;
+
+        info!("Event log replay complete");
+        Ok(())
+    }
+
+    /// Deserializes an event's `data` payload as `T`, if present and valid.
+    fn snapshot<T: DeserializeOwned>(event: &Event) -> Option<T> {
+        event
+            .data
+            .as_ref()
+            .and_then(|data| serde_json::from_value(data.clone()).ok())
+    }
+
+    async fn save_watermarks(&self) -> Result<()> {
+        self.cache
+            .set_sync_watermark("plans", self.storage.plans_count().await?)
+            .await?;
+        self.cache
+            .set_sync_watermark("tasks", self.storage.tasks_count().await?)
+            .await?;
+        self.cache
+            .set_sync_watermark("context", self.storage.context_count().await?)
+            .await?;
+        self.cache
+            .set_sync_watermark("events", self.storage.events_count().await?)
+            .await?;
+        self.cache
+            .set_sync_watermark("reviews", self.storage.reviews_count().await?)
+            .await?;
+        Ok(())
+    }
+
     pub async fn append_plan(&self, plan: &Plan) -> Result<()> {
-        self.storage.append_plan(plan)?;
+        self.storage.append_plan(plan.clone()).await?;
         self.cache.upsert_plan(plan).await?;
         Ok(())
     }
 
     pub async fn append_task(&self, task: &Task) -> Result<()> {
-        self.storage.append_task(task)?;
+        self.storage.append_task(task.clone()).await?;
         self.cache.upsert_task(task).await?;
         Ok(())
     }
 
     pub async fn append_context(&self, context: &ContextItem) -> Result<()> {
-        self.storage.append_context(context)?;
+        self.storage.append_context(context.clone()).await?;
         self.cache.upsert_context(context).await?;
         Ok(())
     }
 
     pub async fn append_event(&self, event: &Event) -> Result<()> {
-        self.storage.append_event(event)?;
+        self.storage.append_event(event.clone()).await?;
         self.cache.upsert_event(event).await?;
+
+        let notifier = Notifier::new(self.config.notifier_config());
+        notifier.notify(event)?;
+
         Ok(())
     }
 
     pub async fn append_review(&self, review: &Review) -> Result<()> {
-        self.storage.append_review(review)?;
+        self.storage.append_review(review.clone()).await?;
         self.cache.upsert_review(review).await?;
         Ok(())
     }
@@ -140,6 +348,14 @@ impl CrocEngine {
         self.cache.get_tasks_for_plan(plan_id).await
     }
 
+    pub async fn ready_tasks(&self, plan_id: &str) -> Result<Vec<Task>> {
+        self.cache.ready_tasks(plan_id).await
+    }
+
+    pub async fn latest_review(&self, plan_id: &str) -> Result<Option<Review>> {
+        self.cache.latest_review(plan_id).await
+    }
+
     pub async fn get_context_for_plan(&self, plan_id: &str) -> Result<Vec<ContextItem>> {
         self.cache.get_context_for_plan(plan_id).await
     }
@@ -159,4 +375,182 @@ impl CrocEngine {
     pub fn config(&self) -> &Config {
         &self.config
     }
+
+    /// Reconstructs active/wall-clock time per task from the plan's event log.
+    pub async fn plan_timesheet(&self, plan_id: &str) -> Result<PlanTimesheet> {
+        let events: Vec<Event> = self
+            .storage
+            .read_events()
+            .await?
+            .into_iter()
+            .filter(|e| e.plan_id.as_deref() == Some(plan_id))
+            .collect();
+
+        Ok(report::build_plan_timesheet(plan_id, &events))
+    }
+
+    /// Spawns a worker for every currently-ready task in `plan_id` (bounded
+    /// by the jobserver's token pool) and returns the ids actually launched.
+    /// Fails with `SchedulerExhausted` if the DAG has deadlocked.
+    pub async fn spawn_ready_workers(&self, plan_id: &str) -> Result<Vec<String>> {
+        let scheduler = Scheduler::new();
+        match scheduler.tick(self, plan_id).await {
+            Ok(result) => Ok(result.spawned),
+            Err(CrocError::DependencyCycle { plan_id, tasks }) => {
+                Err(CrocError::SchedulerExhausted {
+                    plan_id,
+                    remaining: tasks,
+                })
+            }
+            Err(other) => Err(other),
+        }
+    }
+
+    /// Parses a worker's trailing `---CROC_STATUS---` block, persisting any
+    /// facts/decisions it learned as `ContextItem`s and advancing the task's
+    /// status to match. Returns `Ok(None)` if `raw` carries no status block.
+    pub async fn ingest_worker_status(
+        &self,
+        subtask_id: &str,
+        raw: &str,
+    ) -> Result<Option<WorkerStatus>> {
+        let Some(parsed) = status::parse_worker_status(raw) else {
+            return Ok(None);
+        };
+
+        let mut task = self.get_task(subtask_id).await?;
+
+        let accepted = match crate::hooks::HookEngine::load(&self.config.hooks_file())? {
+            Some(hooks) => hooks.on_worker_status(&parsed)?,
+            None => true,
+        };
+
+        for fact in &parsed.facts_learned {
+            let item = ContextItem::new_fact(
+                task.plan_id.clone(),
+                Some(subtask_id.to_string()),
+                fact.content.clone(),
+                fact.source.clone(),
+                None,
+            );
+            self.append_context(&item).await?;
+        }
+
+        for decision in &parsed.decisions_made {
+            let item = ContextItem::new_decision(
+                task.plan_id.clone(),
+                Some(subtask_id.to_string()),
+                decision.decision.clone(),
+                decision.reasoning.clone(),
+                None,
+            );
+            self.append_context(&item).await?;
+        }
+
+        task.status = if !accepted {
+            info!(subtask_id, "on_worker_status hook rejected completion, keeping task running");
+            TaskStatus::Running
+        } else {
+            match parsed.status.as_str() {
+                "complete" => TaskStatus::Complete,
+                "blocked" => TaskStatus::Blocked,
+                _ => TaskStatus::Running,
+            }
+        };
+        task.updated_at = Utc::now();
+        self.append_task(&task).await?;
+
+        let event = Event::new(EventType::WorkerProgress)
+            .with_plan(task.plan_id.clone())
+            .with_task(subtask_id.to_string())
+            .with_data(serde_json::json!({
+                "files_modified": parsed.files_modified,
+                "work_completed": parsed.work_completed,
+                "context_usage": parsed.context_usage,
+            }));
+        self.append_event(&event).await?;
+
+        Ok(Some(parsed))
+    }
+
+    /// Appends one line of live agent output for `task_id` straight to the
+    /// SQLite log store; unlike the other append_* methods there is no
+    /// matching JSONL file, since the cache is the source of truth here.
+    pub async fn append_log(
+        &self,
+        task_id: &str,
+        role: Role,
+        stream: LogStream,
+        line: &str,
+    ) -> Result<LogRecord> {
+        LogSink::append(&self.cache, task_id, role, stream, line).await
+    }
+
+    /// The full recorded log for `task_id`, in order.
+    pub async fn stream_logs(&self, task_id: &str) -> Result<Vec<LogRecord>> {
+        self.cache.get_logs_since(task_id, 0).await
+    }
+
+    /// Log lines for `task_id` appended after `seq`, for resuming a tail.
+    pub async fn logs_since(&self, task_id: &str, seq: u64) -> Result<Vec<LogRecord>> {
+        self.cache.get_logs_since(task_id, seq).await
+    }
+
+    /// Deduplicates concurrent identical operations under `key`: the first
+    /// caller runs `fut`, and any caller arriving while it's still running
+    /// shares that result instead of repeating the work (e.g. an LLM call
+    /// or worktree mutation triggered twice for the same task).
+    pub async fn process_once<T, Fut>(&self, key: impl Into<String>, fut: Fut) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let value = self
+            .process_map
+            .run(key.into(), async { Ok(serde_json::to_value(fut.await?)?) })
+            .await?;
+
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Materializes every due recurring plan template into a fresh concrete
+    /// plan, returning the new plans' ids. A template's `next_run_at` is
+    /// advanced and persisted *before* its concrete plan is created, so a
+    /// crash between the two favors a missed firing over a duplicate one.
+    pub async fn run_scheduled_plans(&self) -> Result<Vec<String>> {
+        let due = self.cache.get_due_scheduled_plans(Utc::now()).await?;
+        let mut materialized = Vec::new();
+
+        for mut template in due {
+            let Some(cron_schedule) = template.cron_schedule.clone() else {
+                continue;
+            };
+            let schedule = CronSchedule::parse(&cron_schedule)?;
+            let fire_time = template.next_run_at.unwrap_or_else(Utc::now);
+
+            template.next_run_at = Some(schedule.next_after(fire_time)?);
+            template.updated_at = Utc::now();
+            self.append_plan(&template).await?;
+
+            let mut plan = Plan::new(
+                Plan::generate_id(),
+                template.title.clone(),
+                template.description.clone(),
+            );
+            plan.subtasks_preview = template.subtasks_preview.clone();
+            plan.considerations = template.considerations.clone();
+            plan.status = PlanStatus::Pending;
+            self.append_plan(&plan).await?;
+
+            let event = Event::new(EventType::PlanCreated)
+                .with_plan(plan.id.clone())
+                .with_data(serde_json::to_value(&plan)?);
+            self.append_event(&event).await?;
+
+            info!(plan_id = %plan.id, template_id = %template.id, "Materialized scheduled plan");
+            materialized.push(plan.id);
+        }
+
+        Ok(materialized)
+    }
 }
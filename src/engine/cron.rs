@@ -0,0 +1,213 @@
+//! A minimal 5- or 6-field cron expression parser (`sec? min hour dom month dow`)
+//! for recurring plans. Rather than computing the next occurrence
+//! analytically, `next_after` walks forward minute by minute, which keeps
+//! the implementation simple and correct for the ranges/steps/lists crocodile
+//! actually needs.
+//!
+//! Day-of-month and day-of-week follow standard crontab(5) semantics, not a
+//! plain AND of both fields: when both are restricted (anything but a bare
+//! `*`), a match on either one fires the job, e.g. `0 0 1,15 * 1` means
+//! "midnight on the 1st/15th, or every Monday," not "only when the 1st/15th
+//! is a Monday."
+
+use crate::error::{CrocError, Result};
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use std::collections::HashSet;
+
+/// How far ahead to search before giving up on an expression that can
+/// never match (e.g. "31 2 *" for a day that February never has).
+const MAX_LOOKAHEAD_MINUTES: i64 = 4 * 366 * 24 * 60;
+
+/// A parsed cron expression. Minute is the unit of search; when a seconds
+/// field is present, the smallest matching second within the matched minute
+/// is used rather than searching second by second.
+pub struct CronSchedule {
+    seconds: HashSet<u32>,
+    minutes: HashSet<u32>,
+    hours: HashSet<u32>,
+    days_of_month: HashSet<u32>,
+    months: HashSet<u32>,
+    days_of_week: HashSet<u32>,
+    /// Whether the day-of-month/day-of-week fields were given as anything
+    /// other than a bare `*`. Per crontab(5), when *both* are restricted
+    /// they're combined with OR rather than AND (see `next_after`).
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(expr: &str) -> Result<Self> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let (seconds_spec, rest): (&str, &[&str]) = match fields.len() {
+            5 => ("0", &fields[..]),
+            6 => (fields[0], &fields[1..]),
+            _ => {
+                return Err(CrocError::InvalidConfig {
+                    reason: format!("cron expression '{}' must have 5 or 6 fields", expr),
+                });
+            }
+        };
+
+        Ok(Self {
+            seconds: parse_field(seconds_spec, 0, 59)?,
+            minutes: parse_field(rest[0], 0, 59)?,
+            hours: parse_field(rest[1], 0, 23)?,
+            days_of_month: parse_field(rest[2], 1, 31)?,
+            months: parse_field(rest[3], 1, 12)?,
+            days_of_week: parse_field(rest[4], 0, 6)?,
+            dom_restricted: rest[2] != "*",
+            dow_restricted: rest[4] != "*",
+        })
+    }
+
+    /// The first firing time from the start of the minute following `after`
+    /// onward. A seconds-field firing later in `after`'s own minute is not
+    /// returned, since the search starts at the next whole minute.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Result<DateTime<Utc>> {
+        let mut candidate = truncate_to_minute(after + Duration::minutes(1));
+
+        for _ in 0..MAX_LOOKAHEAD_MINUTES {
+            let dom_match = self.days_of_month.contains(&candidate.day());
+            let dow_match = self
+                .days_of_week
+                .contains(&candidate.weekday().num_days_from_sunday());
+            // crontab(5): when day-of-month and day-of-week are *both*
+            // restricted (anything but a bare `*`), a match on *either* one
+            // fires the job. If only one (or neither) is restricted, the
+            // unrestricted field already matches every day, so ANDing them
+            // reduces to "match the restricted field" (or "match always").
+            let day_match = if self.dom_restricted && self.dow_restricted {
+                dom_match || dow_match
+            } else {
+                dom_match && dow_match
+            };
+
+            if self.months.contains(&candidate.month())
+                && day_match
+                && self.hours.contains(&candidate.hour())
+                && self.minutes.contains(&candidate.minute())
+            {
+                let second = self.seconds.iter().min().copied().unwrap_or(0);
+                return Ok(candidate.with_second(second).unwrap_or(candidate));
+            }
+            candidate += Duration::minutes(1);
+        }
+
+        Err(CrocError::InvalidConfig {
+            reason: "cron expression did not fire within the lookahead window".to_string(),
+        })
+    }
+}
+
+fn truncate_to_minute(t: DateTime<Utc>) -> DateTime<Utc> {
+    t.with_second(0).and_then(|t| t.with_nanosecond(0)).unwrap_or(t)
+}
+
+/// Parses one field: comma-separated list of `*`, `n`, `a-b`, or any of
+/// those suffixed with `/step`.
+fn parse_field(spec: &str, min: u32, max: u32) -> Result<HashSet<u32>> {
+    let mut values = HashSet::new();
+
+    for part in spec.split(',') {
+        let (range_part, step) = match part.split_once('/') {
+            Some((range_part, step)) => (
+                range_part,
+                step.parse::<u32>().map_err(|_| invalid_field(spec))?,
+            ),
+            None => (part, 1),
+        };
+
+        let (lo, hi) = if range_part == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range_part.split_once('-') {
+            (
+                a.parse::<u32>().map_err(|_| invalid_field(spec))?,
+                b.parse::<u32>().map_err(|_| invalid_field(spec))?,
+            )
+        } else {
+            let value = range_part.parse::<u32>().map_err(|_| invalid_field(spec))?;
+            (value, value)
+        };
+
+        if step == 0 || lo > hi || lo < min || hi > max {
+            return Err(invalid_field(spec));
+        }
+
+        let mut value = lo;
+        while value <= hi {
+            values.insert(value);
+            value += step;
+        }
+    }
+
+    if values.is_empty() {
+        return Err(invalid_field(spec));
+    }
+
+    Ok(values)
+}
+
+fn invalid_field(spec: &str) -> CrocError {
+    CrocError::InvalidConfig {
+        reason: format!("invalid cron field '{}'", spec),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn daily_midnight_fires_at_next_midnight() {
+        let schedule = CronSchedule::parse("0 0 * * *").unwrap();
+        let next = schedule.next_after(at(2026, 7, 27, 10, 0)).unwrap();
+        assert_eq!(next, at(2026, 7, 28, 0, 0));
+    }
+
+    #[test]
+    fn every_fifteen_minutes_steps_forward() {
+        let schedule = CronSchedule::parse("*/15 * * * *").unwrap();
+        let next = schedule.next_after(at(2026, 7, 27, 10, 1)).unwrap();
+        assert_eq!(next, at(2026, 7, 27, 10, 15));
+    }
+
+    #[test]
+    fn six_field_expression_includes_seconds() {
+        let schedule = CronSchedule::parse("30 0 0 * * *").unwrap();
+        let next = schedule.next_after(at(2026, 7, 27, 23, 59));
+        assert_eq!(next.unwrap().second(), 30);
+    }
+
+    #[test]
+    fn dom_and_dow_combine_with_or_when_both_restricted() {
+        let schedule = CronSchedule::parse("0 0 10 * 1").unwrap();
+        // 2026-07-27 is a Monday; the next Monday (2026-08-03) comes before
+        // the next 10th-of-the-month (2026-08-10). Under a plain AND this
+        // would skip straight to 2026-08-10 because the 3rd isn't also the
+        // 10th; under crontab(5) OR semantics it fires on the Monday.
+        let next = schedule.next_after(at(2026, 7, 27, 10, 0)).unwrap();
+        assert_eq!(next, at(2026, 8, 3, 0, 0));
+    }
+
+    #[test]
+    fn dom_alone_restricted_ignores_dow() {
+        let schedule = CronSchedule::parse("0 0 5 * *").unwrap();
+        let next = schedule.next_after(at(2026, 7, 27, 10, 0)).unwrap();
+        assert_eq!(next, at(2026, 8, 5, 0, 0));
+    }
+
+    #[test]
+    fn rejects_wrong_field_count() {
+        assert!(CronSchedule::parse("* * *").is_err());
+    }
+
+    #[test]
+    fn rejects_out_of_range_value() {
+        assert!(CronSchedule::parse("0 0 32 * *").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::error::{CrocError, Result};
+use fs2::FileExt;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::debug;
+
+/// GNU-make-style token pool shared across every `croc` process in a repo,
+/// so the scheduler never oversubscribes the machine regardless of how
+/// many plans or invocations are running concurrently.
+///
+/// Tokens are modeled as bytes in a file under `.croc` rather than a FIFO,
+/// since the crate already takes exclusive-lock-protected file access as
+/// its concurrency primitive (see `JsonlBackend::append_jsonl_locked`).
+pub struct JobServer {
+    path: PathBuf,
+    poll_interval: Duration,
+}
+
+/// A held token; releases its byte back to the pool on drop (including on
+/// panic) so a crashed worker never permanently starves the pool.
+pub struct JobToken {
+    path: PathBuf,
+}
+
+impl JobServer {
+    /// Opens (creating if necessary) the token pool at `path`, sized to
+    /// `max_parallel` tokens. Re-running with a different `max_parallel`
+    /// against an existing file leaves the existing token count untouched.
+    pub fn new(path: PathBuf, max_parallel: usize) -> Result<Self> {
+        if !path.exists() {
+            debug!(path = %path.display(), tokens = max_parallel, "Creating jobserver token pool");
+            let mut file = File::create(&path).map_err(|e| CrocError::Jobserver {
+                message: format!("Failed to create jobserver file {}: {}", path.display(), e),
+            })?;
+            file.write_all(&vec![0u8; max_parallel])
+                .map_err(|e| CrocError::Jobserver {
+                    message: format!("Failed to initialize jobserver tokens: {}", e),
+                })?;
+        }
+
+        Ok(Self {
+            path,
+            poll_interval: Duration::from_millis(100),
+        })
+    }
+
+    /// Blocks until a token is available, then returns it held.
+    pub fn acquire(&self) -> Result<JobToken> {
+        loop {
+            if let Some(token) = self.try_acquire()? {
+                return Ok(token);
+            }
+            std::thread::sleep(self.poll_interval);
+        }
+    }
+
+    /// Attempts to take one token without blocking; `None` if the pool is empty.
+    pub fn try_acquire(&self) -> Result<Option<JobToken>> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&self.path)
+            .map_err(|e| CrocError::Jobserver {
+                message: format!("Failed to open jobserver file {}: {}", self.path.display(), e),
+            })?;
+
+        file.lock_exclusive().map_err(|e| CrocError::Jobserver {
+            message: format!("Failed to lock jobserver file: {}", e),
+        })?;
+
+        let len = file
+            .metadata()
+            .map_err(|e| CrocError::Jobserver {
+                message: format!("Failed to stat jobserver file: {}", e),
+            })?
+            .len();
+
+        if len == 0 {
+            file.unlock().ok();
+            return Ok(None);
+        }
+
+        file.set_len(len - 1).map_err(|e| CrocError::Jobserver {
+            message: format!("Failed to consume jobserver token: {}", e),
+        })?;
+
+        file.unlock().map_err(|e| CrocError::Jobserver {
+            message: format!("Failed to unlock jobserver file: {}", e),
+        })?;
+
+        Ok(Some(JobToken {
+            path: self.path.clone(),
+        }))
+    }
+
+    /// Releases one token back to the pool without holding a `JobToken`,
+    /// used by the supervisor when it observes a worker session end.
+    pub fn release_token(&self) -> Result<()> {
+        Self::release(&self.path)
+    }
+
+    fn release(path: &Path) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|e| CrocError::Jobserver {
+                message: format!("Failed to reopen jobserver file {}: {}", path.display(), e),
+            })?;
+
+        file.lock_exclusive().map_err(|e| CrocError::Jobserver {
+            message: format!("Failed to lock jobserver file for release: {}", e),
+        })?;
+
+        file.seek(SeekFrom::End(0)).map_err(|e| CrocError::Jobserver {
+            message: format!("Failed to seek jobserver file: {}", e),
+        })?;
+        file.write_all(&[0u8]).map_err(|e| CrocError::Jobserver {
+            message: format!("Failed to release jobserver token: {}", e),
+        })?;
+
+        file.unlock().map_err(|e| CrocError::Jobserver {
+            message: format!("Failed to unlock jobserver file after release: {}", e),
+        })?;
+
+        Ok(())
+    }
+}
+
+impl Drop for JobToken {
+    fn drop(&mut self) {
+        if let Err(err) = JobServer::release(&self.path) {
+            tracing::error!(error = %err, "Failed to release jobserver token on drop");
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn remaining_tokens(path: &Path) -> std::io::Result<u64> {
+    let mut file = File::open(path)?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    Ok(buf.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn acquire_consumes_a_token_and_release_restores_it() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("jobserver");
+        let server = JobServer::new(path.clone(), 2).unwrap();
+
+        assert_eq!(remaining_tokens(&path).unwrap(), 2);
+
+        let token = server.try_acquire().unwrap().expect("token available");
+        assert_eq!(remaining_tokens(&path).unwrap(), 1);
+
+        drop(token);
+        assert_eq!(remaining_tokens(&path).unwrap(), 2);
+    }
+
+    #[test]
+    fn try_acquire_returns_none_when_exhausted() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("jobserver");
+        let server = JobServer::new(path, 1).unwrap();
+
+        let _first = server.try_acquire().unwrap().expect("first token");
+        assert!(server.try_acquire().unwrap().is_none());
+    }
+}
@@ -0,0 +1,24 @@
+use crate::engine::cache::Cache;
+use crate::error::Result;
+use crate::schemas::{LogRecord, LogStream, Role};
+
+/// Appends timestamped log lines to the `logs` table, assigning each one
+/// the next sequence number for its task so readers can resume a tail with
+/// `logs_since` instead of re-reading everything.
+pub struct LogSink;
+
+impl LogSink {
+    /// Appends a single line for `task_id` and returns the record as stored.
+    pub async fn append(
+        cache: &Cache,
+        task_id: &str,
+        role: Role,
+        stream: LogStream,
+        line: &str,
+    ) -> Result<LogRecord> {
+        let seq = cache.next_log_seq(task_id).await?;
+        let record = LogRecord::new(task_id.to_string(), role, stream, seq, line.to_string());
+        cache.insert_log(&record).await?;
+        Ok(record)
+    }
+}
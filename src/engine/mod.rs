@@ -1,7 +1,23 @@
 pub mod cache;
+pub mod cron;
 pub mod croc_engine;
+pub mod jobserver;
+pub mod log_sink;
+pub mod notifier;
+pub mod process_map;
+pub mod report;
+pub mod scheduler;
 pub mod storage;
+pub mod supervisor;
 
 pub use cache::Cache;
+pub use cron::CronSchedule;
 pub use croc_engine::CrocEngine;
-pub use storage::Storage;
+pub use jobserver::{JobServer, JobToken};
+pub use log_sink::LogSink;
+pub use notifier::{Notifier, NotifierConfig};
+pub use process_map::ProcessMap;
+pub use report::PlanTimesheet;
+pub use scheduler::Scheduler;
+pub use storage::{Storage, StorageBackend};
+pub use supervisor::Supervisor;
@@ -0,0 +1,119 @@
+use crate::error::{CrocError, Result};
+use crate::schemas::{Event, EventType};
+use std::process::Command;
+use tracing::{debug, warn};
+
+/// Which sinks to fire on a notifiable lifecycle event. Every field is
+/// optional/off by default so `croc` stays silent unless the user opts in.
+#[derive(Debug, Clone, Default)]
+pub struct NotifierConfig {
+    pub webhook_url: Option<String>,
+    pub shell_hook: Option<String>,
+    pub bell: bool,
+}
+
+impl NotifierConfig {
+    pub fn from_env() -> Self {
+        Self {
+            webhook_url: std::env::var("CROC_NOTIFY_WEBHOOK").ok(),
+            shell_hook: std::env::var("CROC_NOTIFY_SHELL_HOOK").ok(),
+            bell: std::env::var("CROC_NOTIFY_BELL")
+                .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Fires configured sinks (webhook, shell hook, terminal bell) for the
+/// lifecycle transitions the roles already describe: a plan getting
+/// approved, a plan completing, a worker getting blocked, and a review
+/// landing as approved or changes-requested.
+pub struct Notifier {
+    config: NotifierConfig,
+}
+
+impl Notifier {
+    pub fn new(config: NotifierConfig) -> Self {
+        Self { config }
+    }
+
+    /// Dispatches `event` to every configured sink, if it's one worth
+    /// surfacing to someone not watching tmux.
+    pub fn notify(&self, event: &Event) -> Result<()> {
+        if !Self::is_notifiable(event) {
+            return Ok(());
+        }
+
+        if let Some(url) = &self.config.webhook_url {
+            self.fire_webhook(url, event)?;
+        }
+
+        if let Some(cmd) = &self.config.shell_hook {
+            self.fire_shell_hook(cmd, event)?;
+        }
+
+        if self.config.bell {
+            Self::ring_bell();
+        }
+
+        Ok(())
+    }
+
+    fn is_notifiable(event: &Event) -> bool {
+        matches!(
+            event.event_type,
+            EventType::PlanApproved
+                | EventType::PlanComplete
+                | EventType::WorkerBlocked
+                | EventType::ReviewApproved
+                | EventType::ReviewChangesRequested
+        )
+    }
+
+    fn fire_webhook(&self, url: &str, event: &Event) -> Result<()> {
+        let payload = serde_json::to_string(event)?;
+        debug!(url, "Firing notifier webhook");
+
+        let status = Command::new("curl")
+            .args(["-s", "-o", "/dev/null", "-w", "%{http_code}"])
+            .args(["-X", "POST"])
+            .args(["-H", "Content-Type: application/json"])
+            .args(["-d", &payload])
+            .arg(url)
+            .status()
+            .map_err(|e| CrocError::Notifier {
+                message: format!("Failed to invoke curl for webhook: {}", e),
+            })?;
+
+        if !status.success() {
+            warn!(url, ?status, "Notifier webhook exited non-zero");
+        }
+
+        Ok(())
+    }
+
+    fn fire_shell_hook(&self, cmd: &str, event: &Event) -> Result<()> {
+        debug!(cmd, "Firing notifier shell hook");
+
+        let status = Command::new("sh")
+            .arg("-c")
+            .arg(cmd)
+            .env("CROC_EVENT_TYPE", serde_json::to_string(&event.event_type)?)
+            .env("CROC_PLAN_ID", event.plan_id.clone().unwrap_or_default())
+            .env("CROC_SUBTASK_ID", event.task_id.clone().unwrap_or_default())
+            .status()
+            .map_err(|e| CrocError::Notifier {
+                message: format!("Failed to run shell hook: {}", e),
+            })?;
+
+        if !status.success() {
+            warn!(cmd, ?status, "Notifier shell hook exited non-zero");
+        }
+
+        Ok(())
+    }
+
+    fn ring_bell() {
+        print!("\x07");
+    }
+}
@@ -0,0 +1,130 @@
+//! Deduplicates concurrent identical operations so two callers racing on the
+//! same key (e.g. an LLM call or worktree mutation for the same task) only
+//! do the work once. The first caller to register a key becomes the leader
+//! and runs the future; everyone else joins a broadcast of its result.
+
+use crate::error::{CrocError, Result};
+use dashmap::mapref::entry::Entry;
+use dashmap::DashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Capacity for each key's broadcast channel. A leader only ever sends one
+/// message (its result) before the entry is torn down, so this just needs
+/// to be at least 1 -- it's not a cap on subscriber count.
+const RESULT_CHANNEL_CAPACITY: usize = 1;
+
+struct InFlight<T> {
+    sender: broadcast::Sender<Arc<Result<T>>>,
+}
+
+enum Role<T> {
+    Leader {
+        sender: broadcast::Sender<Arc<Result<T>>>,
+    },
+    Follower {
+        receiver: broadcast::Receiver<Arc<Result<T>>>,
+    },
+}
+
+pub struct ProcessMap<K, T> {
+    inflight: DashMap<K, InFlight<T>>,
+}
+
+impl<K, T> ProcessMap<K, T>
+where
+    K: Eq + Hash + Clone,
+    T: Clone,
+{
+    pub fn new() -> Self {
+        Self {
+            inflight: DashMap::new(),
+        }
+    }
+
+    /// Runs `fut` under `key`, or, if another caller is already running it,
+    /// waits for that caller's result instead of re-running the work.
+    pub async fn run<Fut>(&self, key: K, fut: Fut) -> Result<T>
+    where
+        Fut: Future<Output = Result<T>>,
+    {
+        let role = match self.inflight.entry(key.clone()) {
+            Entry::Occupied(entry) => Role::Follower {
+                receiver: entry.get().sender.subscribe(),
+            },
+            Entry::Vacant(entry) => {
+                let (sender, _) = broadcast::channel(RESULT_CHANNEL_CAPACITY);
+                entry.insert(InFlight { sender: sender.clone() });
+                Role::Leader { sender }
+            }
+        };
+
+        let sender = match role {
+            Role::Follower { mut receiver } => {
+                return match receiver.recv().await {
+                    Ok(shared) => (*shared).clone(),
+                    Err(_) => Err(CrocError::Cache {
+                        message: "in-flight operation vanished before completing".to_string(),
+                    }),
+                };
+            }
+            Role::Leader { sender } => sender,
+        };
+
+        // Removes the entry if `fut` panics, so a leader that never finishes
+        // doesn't wedge its followers forever -- dropping the entry drops
+        // its `sender`, which closes the channel and turns a follower's
+        // `recv()` into the "vanished" error above instead of a permanent
+        // hang. Disarmed below once `fut` returns normally, since from
+        // there the removal is handled explicitly as part of the broadcast.
+        let mut guard = LeaderGuard {
+            map: &self.inflight,
+            key: Some(key),
+        };
+
+        let result = fut.await;
+        let key = guard.key.take().expect("guard key still present after fut.await");
+        let shared = Arc::new(result.clone());
+
+        // Broadcast and remove the entry under the same `entry()` call, so
+        // the two happen atomically with respect to any other caller's
+        // `entry()` for this key: a follower that subscribes before this
+        // block runs is guaranteed to see the send below, and a caller that
+        // only reaches `entry()` after this block has released the key's
+        // shard lock is guaranteed to find the entry gone and correctly
+        // start a fresh run rather than missing a send that already
+        // happened. There's no `.await` inside the block, so nothing can
+        // interleave between the send and the removal.
+        if let Entry::Occupied(entry) = self.inflight.entry(key) {
+            let _ = entry.get().sender.send(shared);
+            entry.remove();
+        }
+
+        result
+    }
+}
+
+impl<K, T> Default for ProcessMap<K, T>
+where
+    K: Eq + Hash + Clone,
+    T: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct LeaderGuard<'a, K: Eq + Hash, T> {
+    map: &'a DashMap<K, InFlight<T>>,
+    key: Option<K>,
+}
+
+impl<K: Eq + Hash, T> Drop for LeaderGuard<'_, K, T> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.map.remove(&key);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::schemas::{Event, EventType};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Time spent on a single task, reconstructed from its spawn/complete events.
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskTimesheet {
+    pub task_id: String,
+    pub wall_clock_secs: i64,
+    pub active_secs: i64,
+}
+
+/// Time spent across an entire plan.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlanTimesheet {
+    pub plan_id: String,
+    pub total_wall_clock_secs: i64,
+    pub total_active_secs: i64,
+    pub tasks: Vec<TaskTimesheet>,
+}
+
+/// Builds a timesheet for `plan_id` from its ordered event log.
+pub fn build_plan_timesheet(plan_id: &str, events: &[Event]) -> PlanTimesheet {
+    let intervals = task_intervals(events);
+
+    let mut tasks: Vec<TaskTimesheet> = intervals
+        .into_iter()
+        .map(|(task_id, spans)| {
+            let active_secs: i64 = spans.iter().map(|(start, end)| (*end - *start).num_seconds()).sum();
+            let wall_clock_secs = spans
+                .first()
+                .zip(spans.last())
+                .map(|((start, _), (_, end))| (*end - *start).num_seconds())
+                .unwrap_or(0);
+
+            TaskTimesheet {
+                task_id,
+                wall_clock_secs,
+                active_secs,
+            }
+        })
+        .collect();
+
+    tasks.sort_by(|a, b| a.task_id.cmp(&b.task_id));
+
+    let total_wall_clock_secs = tasks.iter().map(|t| t.wall_clock_secs).sum();
+    let total_active_secs = tasks.iter().map(|t| t.active_secs).sum();
+
+    PlanTimesheet {
+        plan_id: plan_id.to_string(),
+        total_wall_clock_secs,
+        total_active_secs,
+        tasks,
+    }
+}
+
+/// Pairs spawn events with their terminating event per task, producing a
+/// list of `(start, end)` spans. An unterminated span is closed at `Utc::now()`.
+fn task_intervals(events: &[Event]) -> HashMap<String, Vec<(DateTime<Utc>, DateTime<Utc>)>> {
+    let mut by_task: HashMap<String, Vec<&Event>> = HashMap::new();
+    for event in events {
+        if let Some(task_id) = &event.task_id {
+            by_task.entry(task_id.clone()).or_default().push(event);
+        }
+    }
+
+    let mut result = HashMap::new();
+    for (task_id, mut task_events) in by_task {
+        task_events.sort_by_key(|e| e.timestamp);
+
+        let mut spans = Vec::new();
+        let mut open: Option<DateTime<Utc>> = None;
+
+        for event in task_events {
+            match event.event_type {
+                EventType::ForemanSpawned | EventType::WorkerSpawned => {
+                    open = Some(event.timestamp);
+                }
+                EventType::WorkerComplete | EventType::WorkerFailed | EventType::PlanCancelled => {
+                    if let Some(start) = open.take() {
+                        spans.push((start, event.timestamp));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(start) = open {
+            spans.push((start, Utc::now()));
+        }
+
+        result.insert(task_id, spans);
+    }
+
+    result
+}
+
+/// Renders a timesheet as a human-readable table.
+pub fn render_table(sheet: &PlanTimesheet) -> String {
+    let mut out = format!(
+        "Plan {}\n{:<16} {:>12} {:>12}\n",
+        sheet.plan_id, "TASK", "WALL (s)", "ACTIVE (s)"
+    );
+
+    for task in &sheet.tasks {
+        out.push_str(&format!(
+            "{:<16} {:>12} {:>12}\n",
+            task.task_id, task.wall_clock_secs, task.active_secs
+        ));
+    }
+
+    out.push_str(&format!(
+        "\nTotal wall-clock: {}s, total active: {}s\n",
+        sheet.total_wall_clock_secs, sheet.total_active_secs
+    ));
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::Event;
+
+    fn event(event_type: EventType, task_id: &str, ts: DateTime<Utc>) -> Event {
+        Event::new(event_type).with_task(task_id.to_string()).with_ts(ts)
+    }
+
+    #[test]
+    fn pairs_spawn_and_complete_into_a_span() {
+        let start = Utc::now();
+        let end = start + chrono::Duration::seconds(120);
+        let events = vec![
+            event(EventType::WorkerSpawned, "task-1.1", start),
+            event(EventType::WorkerComplete, "task-1.1", end),
+        ];
+
+        let intervals = task_intervals(&events);
+        let spans = &intervals["task-1.1"];
+        assert_eq!(spans.len(), 1);
+        assert_eq!((spans[0].1 - spans[0].0).num_seconds(), 120);
+    }
+
+    #[test]
+    fn leaves_unterminated_span_open_until_now() {
+        let start = Utc::now() - chrono::Duration::seconds(30);
+        let events = vec![event(EventType::WorkerSpawned, "task-1.1", start)];
+
+        let intervals = task_intervals(&events);
+        let spans = &intervals["task-1.1"];
+        assert_eq!(spans.len(), 1);
+        assert!((spans[0].1 - spans[0].0).num_seconds() >= 30);
+    }
+
+    #[test]
+    fn build_plan_timesheet_sums_active_time() {
+        let start = Utc::now();
+        let end = start + chrono::Duration::seconds(60);
+        let events = vec![
+            event(EventType::WorkerSpawned, "task-1.1", start),
+            event(EventType::WorkerComplete, "task-1.1", end),
+        ];
+
+        let sheet = build_plan_timesheet("plan-1", &events);
+        assert_eq!(sheet.tasks.len(), 1);
+        assert_eq!(sheet.tasks[0].active_secs, 60);
+        assert_eq!(sheet.total_active_secs, 60);
+    }
+}
@@ -0,0 +1,310 @@
+use crate::engine::jobserver::JobServer;
+use crate::engine::CrocEngine;
+use crate::error::{CrocError, Result};
+use crate::hooks::HookEngine;
+use crate::schemas::{Event, EventType, Task, TaskStatus};
+use crate::tmux::{TmuxSession, worker_session_name};
+use std::collections::{HashMap, HashSet, VecDeque};
+use tracing::{debug, info};
+
+/// What a single scheduling tick did.
+#[derive(Debug, Default)]
+pub struct TickResult {
+    /// Ids of tasks for which a worker session was spawned this tick.
+    pub spawned: Vec<String>,
+    /// Ids of tasks that are unreachable because a dependency failed or is
+    /// not a task of the plan.
+    pub blocked: Vec<String>,
+}
+
+/// Drives a plan's task DAG: finds the ready frontier, spawns workers for
+/// it, and propagates failures to dependents instead of spawning them.
+pub struct Scheduler;
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Runs one scheduling tick for `plan_id`: detects cycles, blocks
+    /// transitively-failed dependents, and spawns workers for the ready set.
+    pub async fn tick(&self, engine: &CrocEngine, plan_id: &str) -> Result<TickResult> {
+        let mut tasks = engine.get_tasks_for_plan(plan_id).await?;
+
+        let max_retries = engine.config().max_task_retries();
+        for task in tasks.iter_mut() {
+            if task.status != TaskStatus::Failed || task.retry_count >= max_retries {
+                continue;
+            }
+
+            task.status = TaskStatus::Pending;
+            task.retry_count += 1;
+            engine.append_task(task).await?;
+            info!(
+                plan_id,
+                task_id = %task.id,
+                attempt = task.retry_count,
+                "Re-spawning failed task"
+            );
+        }
+
+        if let Some(cycle) = detect_cycle(&tasks) {
+            return Err(CrocError::DependencyCycle {
+                plan_id: plan_id.to_string(),
+                tasks: cycle,
+            });
+        }
+
+        let hooks = HookEngine::load(&engine.config().hooks_file())?;
+
+        let mut result = TickResult::default();
+        let by_id: HashMap<&str, &Task> = tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+        let blocked = transitively_blocked(&tasks, &by_id);
+
+        for task in &tasks {
+            if task.status != TaskStatus::Pending {
+                continue;
+            }
+
+            if blocked.contains(task.id.as_str()) {
+                let mut blocked_task = task.clone();
+                blocked_task.status = TaskStatus::Blocked;
+                engine.append_task(&blocked_task).await?;
+
+                let event = Event::new(EventType::WorkerBlocked)
+                    .with_plan(plan_id.to_string())
+                    .with_task(task.id.clone());
+                engine.append_event(&event).await?;
+
+                result.blocked.push(task.id.clone());
+                continue;
+            }
+
+            if is_ready(task, &by_id) {
+                if let Some(hooks) = &hooks {
+                    if !hooks.can_spawn(task)? {
+                        debug!(plan_id, task_id = %task.id, "can_spawn hook rejected task, deferring");
+                        continue;
+                    }
+                }
+
+                let jobserver = JobServer::new(
+                    engine.config().jobserver_path(),
+                    engine.config().max_parallel_workers(),
+                )?;
+                let token = match jobserver.try_acquire()? {
+                    Some(token) => token,
+                    None => {
+                        debug!(plan_id, task_id = %task.id, "No jobserver tokens available, deferring");
+                        continue;
+                    }
+                };
+
+                let session = TmuxSession::new(worker_session_name(plan_id, &task.id));
+                session.spawn(
+                    "croc",
+                    &["prime"],
+                    &[
+                        ("CROC_ROLE", "worker"),
+                        ("CROC_PLAN_ID", plan_id),
+                        ("CROC_SUBTASK_ID", &task.id),
+                    ],
+                )?;
+                // The token is intentionally leaked to the worker's lifetime: it is
+                // released by the supervisor when it observes WorkerComplete/Failed.
+                std::mem::forget(token);
+
+                let mut running = task.clone();
+                running.status = TaskStatus::Running;
+                engine.append_task(&running).await?;
+
+                info!(plan_id, task_id = %task.id, "Spawned worker for ready task");
+                result.spawned.push(task.id.clone());
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn is_ready(task: &Task, by_id: &HashMap<&str, &Task>) -> bool {
+    task.depends_on.iter().all(|dep| {
+        by_id
+            .get(dep.as_str())
+            .map(|t| t.status == TaskStatus::Complete)
+            .unwrap_or(false)
+    })
+}
+
+/// Returns the ids of non-failed tasks that can never become ready because a
+/// dependency (direct or transitive) has `Failed` or names a task id that is
+/// not in `tasks`.
+fn transitively_blocked<'a>(tasks: &'a [Task], by_id: &HashMap<&'a str, &'a Task>) -> HashSet<&'a str> {
+    let mut blocked: HashSet<&str> = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Failed)
+        .map(|t| t.id.as_str())
+        .collect();
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for task in tasks {
+            if blocked.contains(task.id.as_str()) {
+                continue;
+            }
+            if task
+                .depends_on
+                .iter()
+                .any(|dep| blocked.contains(dep.as_str()) || !by_id.contains_key(dep.as_str()))
+            {
+                blocked.insert(task.id.as_str());
+                changed = true;
+            }
+        }
+    }
+
+    blocked.retain(|id| {
+        by_id
+            .get(id)
+            .map(|t| t.status != TaskStatus::Failed)
+            .unwrap_or(false)
+    });
+
+    blocked
+}
+
+/// Runs Kahn's algorithm over `depends_on` edges; returns the ids still
+/// stuck in the graph (the cycle plus any task downstream of it) if not
+/// every node could be ordered.
+fn detect_cycle(tasks: &[Task]) -> Option<Vec<String>> {
+    let ids: HashSet<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+    let mut in_degree: HashMap<&str, usize> = tasks.iter().map(|t| (t.id.as_str(), 0)).collect();
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for task in tasks {
+        for dep in &task.depends_on {
+            if !ids.contains(dep.as_str()) {
+                continue;
+            }
+            *in_degree.get_mut(task.id.as_str()).unwrap() += 1;
+            dependents.entry(dep.as_str()).or_default().push(&task.id);
+        }
+    }
+
+    let mut queue: VecDeque<&str> = in_degree
+        .iter()
+        .filter(|(_, degree)| **degree == 0)
+        .map(|(id, _)| *id)
+        .collect();
+
+    let mut visited = 0usize;
+    while let Some(id) = queue.pop_front() {
+        visited += 1;
+        if let Some(next) = dependents.get(id) {
+            for dependent in next {
+                let degree = in_degree.get_mut(dependent).unwrap();
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push_back(dependent);
+                }
+            }
+        }
+    }
+
+    if visited == tasks.len() {
+        debug!("No dependency cycle detected");
+        return None;
+    }
+
+    let remaining: Vec<String> = in_degree
+        .into_iter()
+        .filter(|(_, degree)| *degree > 0)
+        .map(|(id, _)| id.to_string())
+        .collect();
+
+    Some(remaining)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::TaskType;
+
+    fn task(id: &str, depends_on: &[&str], status: TaskStatus) -> Task {
+        let now = chrono::Utc::now();
+        Task {
+            id: id.to_string(),
+            plan_id: "plan-x".to_string(),
+            parent_id: None,
+            task_type: TaskType::Subtask,
+            title: id.to_string(),
+            description: None,
+            status,
+            depends_on: depends_on.iter().map(|s| s.to_string()).collect(),
+            worktree: None,
+            assigned_worker: None,
+            retry_count: 0,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn detect_cycle_returns_none_for_dag() {
+        let tasks = vec![
+            task("a", &[], TaskStatus::Pending),
+            task("b", &["a"], TaskStatus::Pending),
+            task("c", &["b"], TaskStatus::Pending),
+        ];
+        assert!(detect_cycle(&tasks).is_none());
+    }
+
+    #[test]
+    fn detect_cycle_finds_mutual_dependency() {
+        let tasks = vec![
+            task("a", &["b"], TaskStatus::Pending),
+            task("b", &["a"], TaskStatus::Pending),
+        ];
+        let cycle = detect_cycle(&tasks).expect("cycle expected");
+        let mut cycle = cycle;
+        cycle.sort();
+        assert_eq!(cycle, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn is_ready_requires_all_deps_complete() {
+        let a = task("a", &[], TaskStatus::Complete);
+        let b = task("b", &["a"], TaskStatus::Pending);
+        let by_id: HashMap<&str, &Task> = vec![("a", &a), ("b", &b)].into_iter().collect();
+        assert!(is_ready(&b, &by_id));
+    }
+
+    #[test]
+    fn is_ready_false_when_dep_incomplete() {
+        let a = task("a", &[], TaskStatus::Pending);
+        let b = task("b", &["a"], TaskStatus::Pending);
+        let by_id: HashMap<&str, &Task> = vec![("a", &a), ("b", &b)].into_iter().collect();
+        assert!(!is_ready(&b, &by_id));
+    }
+
+    #[test]
+    fn transitively_blocked_propagates_through_chain() {
+        let a = task("a", &[], TaskStatus::Failed);
+        let b = task("b", &["a"], TaskStatus::Pending);
+        let c = task("c", &["b"], TaskStatus::Pending);
+        let tasks = vec![a, b, c];
+        let by_id: HashMap<&str, &Task> = tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+        let blocked = transitively_blocked(&tasks, &by_id);
+        assert!(blocked.contains("b"));
+        assert!(blocked.contains("c"));
+        assert!(!blocked.contains("a"));
+    }
+}
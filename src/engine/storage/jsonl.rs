@@ -0,0 +1,670 @@
+//! The default `StorageBackend`: one append-only JSONL log per entity,
+//! exclusive-locked on write and periodically checkpointed so a reader
+//! doesn't have to replay the whole file. Zero external dependencies beyond
+//! the filesystem, which is why it stays the default; see `sqlite` for the
+//! WAL-backed alternative.
+
+use super::StorageBackend;
+use crate::config::Config;
+use crate::error::{Result, StorageError};
+use crate::schemas::{ContextItem, Event, Plan, Review, Task};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use fs2::FileExt;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Write a checkpoint after this many appends, so a long-lived `.croc` log
+/// never needs more than ~`CHECKPOINT_INTERVAL` lines of tail replay.
+const CHECKPOINT_INTERVAL: usize = 64;
+
+/// Lets `JsonlBackend` fold a JSONL log's records down to "latest record per
+/// id" without knowing the concrete entity types.
+pub trait Checkpointable {
+    fn checkpoint_id(&self) -> &str;
+    fn checkpoint_timestamp(&self) -> DateTime<Utc>;
+
+    /// Whether a checkpoint should keep only the latest record per id. True
+    /// for mutable, id-keyed entities (`Plan`/`Task`), which are re-appended
+    /// on every status change. False for append-only entities (`Event`,
+    /// `ContextItem`, `Review`), which are never updated in place and whose
+    /// ids (generated from a millisecond timestamp) can legitimately collide
+    /// between two distinct records — deduping those by id would silently
+    /// drop one from the log.
+    fn dedupe_by_id() -> bool
+    where
+        Self: Sized;
+}
+
+impl Checkpointable for Plan {
+    fn checkpoint_id(&self) -> &str {
+        &self.id
+    }
+
+    fn checkpoint_timestamp(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    fn dedupe_by_id() -> bool {
+        true
+    }
+}
+
+impl Checkpointable for Task {
+    fn checkpoint_id(&self) -> &str {
+        &self.id
+    }
+
+    fn checkpoint_timestamp(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    fn dedupe_by_id() -> bool {
+        true
+    }
+}
+
+impl Checkpointable for ContextItem {
+    fn checkpoint_id(&self) -> &str {
+        &self.id
+    }
+
+    fn checkpoint_timestamp(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn dedupe_by_id() -> bool {
+        false
+    }
+}
+
+impl Checkpointable for Event {
+    fn checkpoint_id(&self) -> &str {
+        &self.id
+    }
+
+    fn checkpoint_timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    fn dedupe_by_id() -> bool {
+        false
+    }
+}
+
+impl Checkpointable for Review {
+    fn checkpoint_id(&self) -> &str {
+        &self.id
+    }
+
+    fn checkpoint_timestamp(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    fn dedupe_by_id() -> bool {
+        false
+    }
+}
+
+/// The fully-reduced state of a JSONL log as of `line_offset` lines in:
+/// the latest record per id. A reader replays only the tail past
+/// `line_offset` and folds it in as a last-writer-wins update.
+#[derive(Serialize, Deserialize)]
+struct Checkpoint<T> {
+    line_offset: usize,
+    records: HashMap<String, T>,
+}
+
+#[derive(Clone)]
+pub struct JsonlBackend {
+    config: Config,
+}
+
+impl JsonlBackend {
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn append_jsonl_locked<T>(&self, path: &Path, record: &T) -> Result<()>
+    where
+        T: Serialize + DeserializeOwned + Checkpointable,
+    {
+        debug!(path = %path.display(), "Appending to JSONL with lock");
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| StorageError::from_io(path, &e))?;
+
+        file.lock_exclusive().map_err(|e| StorageError::from_io(path, &e))?;
+
+        let json = serde_json::to_string(record)?;
+        writeln!(file, "{}", json).map_err(|e| StorageError::from_io(path, &e))?;
+
+        // Written while the exclusive lock above is still held, so a crash
+        // mid-checkpoint never races a concurrent appender.
+        self.maybe_checkpoint::<T>(path)?;
+
+        file.unlock().map_err(|e| StorageError::from_io(path, &e))?;
+
+        Ok(())
+    }
+
+    /// Every `CHECKPOINT_INTERVAL` lines, folds the log down to "latest
+    /// record per id" and writes it atomically (temp file + rename) as
+    /// `<name>.checkpoint.json` next to `path`.
+    fn maybe_checkpoint<T>(&self, path: &Path) -> Result<()>
+    where
+        T: Serialize + DeserializeOwned + Checkpointable,
+    {
+        let total_lines = self.line_count(path)?;
+        if total_lines == 0 || total_lines % CHECKPOINT_INTERVAL != 0 {
+            return Ok(());
+        }
+
+        let checkpoint_path = Self::checkpoint_path(path);
+        let mut records: HashMap<String, T> = HashMap::new();
+        let mut start_line = 0;
+
+        if let Some(checkpoint) = self.load_checkpoint::<T>(&checkpoint_path) {
+            if checkpoint.line_offset <= total_lines {
+                records = checkpoint.records;
+                start_line = checkpoint.line_offset;
+            }
+        }
+
+        for record in self.read_jsonl_from::<T>(path, start_line)? {
+            Self::merge_record(&mut records, record);
+        }
+
+        self.write_checkpoint(
+            &checkpoint_path,
+            &Checkpoint {
+                line_offset: total_lines,
+                records,
+            },
+        )
+    }
+
+    /// Folds a JSONL log down to its latest-per-id state, replaying only
+    /// the tail past the last checkpoint instead of the whole file. Falls
+    /// back to a full replay if there is no checkpoint, it fails to parse,
+    /// or its offset is past the current file length (the log was
+    /// truncated or rewritten out from under it).
+    fn read_with_checkpoint<T>(&self, path: &Path) -> Result<Vec<T>>
+    where
+        T: DeserializeOwned + Checkpointable,
+    {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let checkpoint_path = Self::checkpoint_path(path);
+        let mut records: HashMap<String, T> = HashMap::new();
+        let mut start_line = 0;
+
+        if let Some(checkpoint) = self.load_checkpoint::<T>(&checkpoint_path) {
+            let total_lines = self.line_count(path)?;
+            if checkpoint.line_offset <= total_lines {
+                records = checkpoint.records;
+                start_line = checkpoint.line_offset;
+            }
+        }
+
+        for record in self.read_jsonl_from::<T>(path, start_line)? {
+            Self::merge_record(&mut records, record);
+        }
+
+        let mut result: Vec<T> = records.into_values().collect();
+        result.sort_by(|a, b| a.checkpoint_timestamp().cmp(&b.checkpoint_timestamp()));
+        Ok(result)
+    }
+
+    fn merge_record<T: Checkpointable>(records: &mut HashMap<String, T>, record: T) {
+        if !T::dedupe_by_id() {
+            // Append-only entity: keep every record. Key on id plus the
+            // current record count rather than id alone, so two records
+            // whose ids collide (same-millisecond creation) both survive
+            // instead of the second silently overwriting the first.
+            let key = format!("{}#{}", record.checkpoint_id(), records.len());
+            records.insert(key, record);
+            return;
+        }
+
+        let id = record.checkpoint_id().to_string();
+        let supersedes = match records.get(&id) {
+            Some(existing) => record.checkpoint_timestamp() >= existing.checkpoint_timestamp(),
+            None => true,
+        };
+        if supersedes {
+            records.insert(id, record);
+        }
+    }
+
+    fn checkpoint_path(path: &Path) -> PathBuf {
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        path.with_file_name(format!("{}.checkpoint.json", stem))
+    }
+
+    fn load_checkpoint<T: DeserializeOwned>(&self, checkpoint_path: &Path) -> Option<Checkpoint<T>> {
+        let contents = std::fs::read_to_string(checkpoint_path).ok()?;
+        serde_json::from_str(&contents).ok()
+    }
+
+    fn write_checkpoint<T: Serialize>(&self, checkpoint_path: &Path, checkpoint: &Checkpoint<T>) -> Result<()> {
+        let tmp_path = checkpoint_path.with_extension("tmp");
+        let json = serde_json::to_string(checkpoint)?;
+
+        std::fs::write(&tmp_path, json).map_err(|e| StorageError::from_io(tmp_path.clone(), &e))?;
+
+        std::fs::rename(&tmp_path, checkpoint_path).map_err(|e| StorageError::from_io(checkpoint_path, &e))?;
+
+        Ok(())
+    }
+
+    pub fn read_jsonl<T: DeserializeOwned>(&self, path: &Path) -> Result<Vec<T>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = File::open(path).map_err(|e| StorageError::from_io(path, &e))?;
+
+        let reader = BufReader::new(file);
+        let mut records = Vec::new();
+
+        for (line_no, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| StorageError::from_io(path, &e))?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            records.push(Self::parse_line(path, line_no, &line)?);
+        }
+
+        Ok(records)
+    }
+
+    /// Reads only the records at or after `start_line` (0-indexed), skipping
+    /// empty lines like `read_jsonl`. Used by the cache's incremental sync
+    /// so it doesn't have to reparse the whole file on every reconcile.
+    pub fn read_jsonl_from<T: DeserializeOwned>(&self, path: &Path, start_line: usize) -> Result<Vec<T>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let file = File::open(path).map_err(|e| StorageError::from_io(path, &e))?;
+
+        let reader = BufReader::new(file);
+        let mut records = Vec::new();
+
+        for (line_no, line) in reader.lines().enumerate().skip(start_line) {
+            let line = line.map_err(|e| StorageError::from_io(path, &e))?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            records.push(Self::parse_line(path, line_no, &line)?);
+        }
+
+        Ok(records)
+    }
+
+    /// Parses a single JSONL line, attaching the 1-indexed line number and
+    /// the offending text to `StorageError::Corrupt` instead of propagating
+    /// a bare `serde_json::Error` that can't say where in the file it is.
+    fn parse_line<T: DeserializeOwned>(path: &Path, line_no: usize, line: &str) -> Result<T> {
+        serde_json::from_str(line)
+            .map_err(|e| {
+                StorageError::Corrupt {
+                    path: path.to_path_buf(),
+                    line: line_no + 1,
+                    source: e.to_string(),
+                }
+                .into()
+            })
+    }
+
+    /// Counts non-empty lines in `path`, used to track the sync watermark.
+    pub fn line_count(&self, path: &Path) -> Result<usize> {
+        if !path.exists() {
+            return Ok(0);
+        }
+
+        let file = File::open(path).map_err(|e| StorageError::from_io(path, &e))?;
+
+        Ok(BufReader::new(file)
+            .lines()
+            .filter_map(|l| l.ok())
+            .filter(|l| !l.trim().is_empty())
+            .count())
+    }
+
+    pub fn create_empty_file(&self, path: &Path) -> Result<()> {
+        File::create(path).map_err(|e| StorageError::from_io(path, &e))?;
+        Ok(())
+    }
+
+    fn append_plan_sync(&self, plan: &Plan) -> Result<()> {
+        self.append_jsonl_locked(&self.config.plans_file(), plan)
+    }
+
+    fn append_task_sync(&self, task: &Task) -> Result<()> {
+        self.append_jsonl_locked(&self.config.tasks_file(), task)
+    }
+
+    fn append_context_sync(&self, context: &ContextItem) -> Result<()> {
+        self.append_jsonl_locked(&self.config.context_file(), context)
+    }
+
+    fn append_event_sync(&self, event: &Event) -> Result<()> {
+        self.append_jsonl_locked(&self.config.events_file(), event)
+    }
+
+    fn append_review_sync(&self, review: &Review) -> Result<()> {
+        self.append_jsonl_locked(&self.config.reviews_file(), review)
+    }
+
+    fn read_plans_sync(&self) -> Result<Vec<Plan>> {
+        self.read_with_checkpoint(&self.config.plans_file())
+    }
+
+    fn read_tasks_sync(&self) -> Result<Vec<Task>> {
+        self.read_with_checkpoint(&self.config.tasks_file())
+    }
+
+    fn read_context_sync(&self) -> Result<Vec<ContextItem>> {
+        self.read_with_checkpoint(&self.config.context_file())
+    }
+
+    fn read_events_sync(&self) -> Result<Vec<Event>> {
+        self.read_with_checkpoint(&self.config.events_file())
+    }
+
+    fn read_reviews_sync(&self) -> Result<Vec<Review>> {
+        self.read_with_checkpoint(&self.config.reviews_file())
+    }
+
+    /// Holds a shared (read) lock on `path` for the duration of `f`, so
+    /// concurrent readers never serialize against each other but still
+    /// block out a concurrent exclusive writer. A no-op if `path` doesn't
+    /// exist yet.
+    fn with_shared_lock<F, R>(&self, path: &Path, f: F) -> Result<R>
+    where
+        F: FnOnce() -> Result<R>,
+    {
+        if !path.exists() {
+            return f();
+        }
+
+        let file = File::open(path).map_err(|e| StorageError::from_io(path, &e))?;
+
+        file.lock_shared().map_err(|e| StorageError::from_io(path, &e))?;
+
+        let result = f();
+
+        file.unlock().map_err(|e| StorageError::from_io(path, &e))?;
+
+        result
+    }
+
+    /// Runs `f` on a blocking-pool thread with a clone of this backend, so
+    /// the exclusive/shared fs2 lock `f` takes never straddles an `.await`
+    /// point in the calling async context.
+    async fn blocking<F, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(JsonlBackend) -> Result<R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let backend = self.clone();
+        tokio::task::spawn_blocking(move || f(backend))
+            .await
+            .map_err(|e| StorageError::Other {
+                message: format!("Blocking storage task panicked: {}", e),
+            })?
+    }
+}
+
+#[async_trait]
+impl StorageBackend for JsonlBackend {
+    async fn initialize(&self) -> Result<()> {
+        let config = self.config.clone();
+        self.blocking(move |backend| {
+            backend.create_empty_file(&config.plans_file())?;
+            backend.create_empty_file(&config.tasks_file())?;
+            backend.create_empty_file(&config.context_file())?;
+            backend.create_empty_file(&config.events_file())?;
+            backend.create_empty_file(&config.reviews_file())?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn append_plan(&self, plan: Plan) -> Result<()> {
+        self.blocking(move |backend| backend.append_plan_sync(&plan)).await
+    }
+
+    async fn append_task(&self, task: Task) -> Result<()> {
+        self.blocking(move |backend| backend.append_task_sync(&task)).await
+    }
+
+    async fn append_context(&self, context: ContextItem) -> Result<()> {
+        self.blocking(move |backend| backend.append_context_sync(&context)).await
+    }
+
+    async fn append_event(&self, event: Event) -> Result<()> {
+        self.blocking(move |backend| backend.append_event_sync(&event)).await
+    }
+
+    async fn append_review(&self, review: Review) -> Result<()> {
+        self.blocking(move |backend| backend.append_review_sync(&review)).await
+    }
+
+    async fn read_plans(&self) -> Result<Vec<Plan>> {
+        let path = self.config.plans_file();
+        self.blocking(move |backend| backend.with_shared_lock(&path, || backend.read_plans_sync()))
+            .await
+    }
+
+    async fn read_tasks(&self) -> Result<Vec<Task>> {
+        let path = self.config.tasks_file();
+        self.blocking(move |backend| backend.with_shared_lock(&path, || backend.read_tasks_sync()))
+            .await
+    }
+
+    async fn read_context(&self) -> Result<Vec<ContextItem>> {
+        let path = self.config.context_file();
+        self.blocking(move |backend| backend.with_shared_lock(&path, || backend.read_context_sync()))
+            .await
+    }
+
+    async fn read_events(&self) -> Result<Vec<Event>> {
+        let path = self.config.events_file();
+        self.blocking(move |backend| backend.with_shared_lock(&path, || backend.read_events_sync()))
+            .await
+    }
+
+    async fn read_reviews(&self) -> Result<Vec<Review>> {
+        let path = self.config.reviews_file();
+        self.blocking(move |backend| backend.with_shared_lock(&path, || backend.read_reviews_sync()))
+            .await
+    }
+
+    async fn plans_count(&self) -> Result<usize> {
+        let path = self.config.plans_file();
+        self.blocking(move |backend| backend.line_count(&path)).await
+    }
+
+    async fn tasks_count(&self) -> Result<usize> {
+        let path = self.config.tasks_file();
+        self.blocking(move |backend| backend.line_count(&path)).await
+    }
+
+    async fn context_count(&self) -> Result<usize> {
+        let path = self.config.context_file();
+        self.blocking(move |backend| backend.line_count(&path)).await
+    }
+
+    async fn events_count(&self) -> Result<usize> {
+        let path = self.config.events_file();
+        self.blocking(move |backend| backend.line_count(&path)).await
+    }
+
+    async fn reviews_count(&self) -> Result<usize> {
+        let path = self.config.reviews_file();
+        self.blocking(move |backend| backend.line_count(&path)).await
+    }
+
+    async fn read_plans_since(&self, watermark: usize) -> Result<Vec<Plan>> {
+        let path = self.config.plans_file();
+        self.blocking(move |backend| backend.with_shared_lock(&path, || backend.read_jsonl_from(&path, watermark)))
+            .await
+    }
+
+    async fn read_tasks_since(&self, watermark: usize) -> Result<Vec<Task>> {
+        let path = self.config.tasks_file();
+        self.blocking(move |backend| backend.with_shared_lock(&path, || backend.read_jsonl_from(&path, watermark)))
+            .await
+    }
+
+    async fn read_context_since(&self, watermark: usize) -> Result<Vec<ContextItem>> {
+        let path = self.config.context_file();
+        self.blocking(move |backend| backend.with_shared_lock(&path, || backend.read_jsonl_from(&path, watermark)))
+            .await
+    }
+
+    async fn read_events_since(&self, watermark: usize) -> Result<Vec<Event>> {
+        let path = self.config.events_file();
+        self.blocking(move |backend| backend.with_shared_lock(&path, || backend.read_jsonl_from(&path, watermark)))
+            .await
+    }
+
+    async fn read_reviews_since(&self, watermark: usize) -> Result<Vec<Review>> {
+        let path = self.config.reviews_file();
+        self.blocking(move |backend| backend.with_shared_lock(&path, || backend.read_jsonl_from(&path, watermark)))
+            .await
+    }
+
+    fn watch_plans(&self) -> Result<flume::Receiver<Plan>> {
+        super::watch::watch_jsonl(self.config.plans_file())
+    }
+
+    fn watch_tasks(&self) -> Result<flume::Receiver<Task>> {
+        super::watch::watch_jsonl(self.config.tasks_file())
+    }
+
+    fn watch_context(&self) -> Result<flume::Receiver<ContextItem>> {
+        super::watch::watch_jsonl(self.config.context_file())
+    }
+
+    fn watch_events(&self) -> Result<flume::Receiver<Event>> {
+        super::watch::watch_jsonl(self.config.events_file())
+    }
+
+    fn watch_reviews(&self) -> Result<flume::Receiver<Review>> {
+        super::watch::watch_jsonl(self.config.reviews_file())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{SchedulerSettings, StorageSettings};
+    use tempfile::TempDir;
+
+    fn test_config(dir: &TempDir) -> Config {
+        Config {
+            croc_dir: dir.path().to_path_buf(),
+            storage: StorageSettings::default(),
+            scheduler: SchedulerSettings::default(),
+        }
+    }
+
+    #[test]
+    fn jsonl_round_trip_preserves_plan_data() {
+        let dir = TempDir::new().unwrap();
+        let backend = JsonlBackend::new(test_config(&dir));
+        let path = dir.path().join("test.jsonl");
+
+        let plan = Plan::new(
+            "plan-test123".to_string(),
+            "Test Plan".to_string(),
+            "A test plan".to_string(),
+        );
+
+        backend.append_jsonl_locked(&path, &plan).unwrap();
+        let plans: Vec<Plan> = backend.read_jsonl(&path).unwrap();
+
+        assert_eq!(plans.len(), 1);
+        assert_eq!(plans[0].id, "plan-test123");
+        assert_eq!(plans[0].title, "Test Plan");
+    }
+
+    #[test]
+    fn jsonl_appends_multiple_records() {
+        let dir = TempDir::new().unwrap();
+        let backend = JsonlBackend::new(test_config(&dir));
+        let path = dir.path().join("test.jsonl");
+
+        for i in 0..3 {
+            let plan = Plan::new(
+                format!("plan-{}", i),
+                format!("Plan {}", i),
+                "desc".to_string(),
+            );
+            backend.append_jsonl_locked(&path, &plan).unwrap();
+        }
+
+        let plans: Vec<Plan> = backend.read_jsonl(&path).unwrap();
+        assert_eq!(plans.len(), 3);
+        assert_eq!(plans[0].id, "plan-0");
+        assert_eq!(plans[2].id, "plan-2");
+    }
+
+    #[test]
+    fn read_jsonl_returns_empty_vec_for_missing_file() {
+        let dir = TempDir::new().unwrap();
+        let backend = JsonlBackend::new(test_config(&dir));
+        let path = dir.path().join("nonexistent.jsonl");
+
+        let plans: Vec<Plan> = backend.read_jsonl(&path).unwrap();
+        assert!(plans.is_empty());
+    }
+
+    #[test]
+    fn read_jsonl_skips_empty_lines() {
+        let dir = TempDir::new().unwrap();
+        let backend = JsonlBackend::new(test_config(&dir));
+        let path = dir.path().join("test.jsonl");
+
+        let plan = Plan::new("plan-1".to_string(), "Plan".to_string(), "desc".to_string());
+        backend.append_jsonl_locked(&path, &plan).unwrap();
+
+        std::fs::write(
+            &path,
+            format!(
+                "{}\n\n{}\n",
+                serde_json::to_string(&plan).unwrap(),
+                serde_json::to_string(&plan).unwrap()
+            ),
+        )
+        .unwrap();
+
+        let plans: Vec<Plan> = backend.read_jsonl(&path).unwrap();
+        assert_eq!(plans.len(), 2);
+    }
+}
@@ -0,0 +1,193 @@
+//! Pluggable persistence for crocodile's five entity logs (`Plan`, `Task`,
+//! `ContextItem`, `Event`, `Review`). `StorageBackend` is the contract every
+//! backend implements; `Storage` is the facade `CrocEngine` talks to, built
+//! from whichever backend `Config::storage` selects.
+
+mod jsonl;
+mod sqlite;
+mod watch;
+
+pub use jsonl::{Checkpointable, JsonlBackend};
+pub use sqlite::SqliteBackend;
+
+use crate::config::{Config, StorageBackendKind};
+use crate::error::Result;
+use crate::schemas::{ContextItem, Event, Plan, Review, Task};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// A durable store for crocodile's entity logs. Plan and task appends are
+/// last-writer-wins per id (favoring newer `updated_at`), and `read_*_since`
+/// takes a watermark in the same units `*_count` reports, so a caller can
+/// track "how much of this log have I already seen" without knowing whether
+/// the backend is counting JSONL lines, SQLite rows, or (as `SqliteBackend`
+/// does) a monotonic sequence number that also advances on updates, so an
+/// update to an already-seen id isn't missed by a watermark taken before it.
+#[async_trait]
+pub trait StorageBackend: Send + Sync {
+    /// Prepares the backend's storage (creates files/tables) so the other
+    /// methods have somewhere to write. Called once by `croc init`.
+    async fn initialize(&self) -> Result<()>;
+
+    async fn append_plan(&self, plan: Plan) -> Result<()>;
+    async fn append_task(&self, task: Task) -> Result<()>;
+    async fn append_context(&self, context: ContextItem) -> Result<()>;
+    async fn append_event(&self, event: Event) -> Result<()>;
+    async fn append_review(&self, review: Review) -> Result<()>;
+
+    async fn read_plans(&self) -> Result<Vec<Plan>>;
+    async fn read_tasks(&self) -> Result<Vec<Task>>;
+    async fn read_context(&self) -> Result<Vec<ContextItem>>;
+    async fn read_events(&self) -> Result<Vec<Event>>;
+    async fn read_reviews(&self) -> Result<Vec<Review>>;
+
+    async fn plans_count(&self) -> Result<usize>;
+    async fn tasks_count(&self) -> Result<usize>;
+    async fn context_count(&self) -> Result<usize>;
+    async fn events_count(&self) -> Result<usize>;
+    async fn reviews_count(&self) -> Result<usize>;
+
+    /// Reads only the records past `watermark` (in the units `*_count`
+    /// reports), in append order, so the cache can reconcile in
+    /// O(new records) instead of replaying the whole log.
+    async fn read_plans_since(&self, watermark: usize) -> Result<Vec<Plan>>;
+    async fn read_tasks_since(&self, watermark: usize) -> Result<Vec<Task>>;
+    async fn read_context_since(&self, watermark: usize) -> Result<Vec<ContextItem>>;
+    async fn read_events_since(&self, watermark: usize) -> Result<Vec<Event>>;
+    async fn read_reviews_since(&self, watermark: usize) -> Result<Vec<Review>>;
+
+    /// Subscribes to records appended from this point on, without affecting
+    /// `read_*`/`read_*_since` watermarks. Setup is synchronous (it doesn't
+    /// wait for the first record), so this isn't an `async fn`; the returned
+    /// channel is where the actual tailing shows up.
+    fn watch_plans(&self) -> Result<flume::Receiver<Plan>>;
+    fn watch_tasks(&self) -> Result<flume::Receiver<Task>>;
+    fn watch_context(&self) -> Result<flume::Receiver<ContextItem>>;
+    fn watch_events(&self) -> Result<flume::Receiver<Event>>;
+    fn watch_reviews(&self) -> Result<flume::Receiver<Review>>;
+}
+
+/// Thin facade `CrocEngine` holds, dispatching to whichever `StorageBackend`
+/// `config.storage.backend` selects.
+#[derive(Clone)]
+pub struct Storage {
+    backend: Arc<dyn StorageBackend>,
+}
+
+impl Storage {
+    /// Connects to the backend selected by `config.storage.backend`. For
+    /// JSONL this is immediate (no I/O); for SQLite it opens (and migrates)
+    /// the pool.
+    pub async fn connect(config: Config) -> Result<Self> {
+        let backend: Arc<dyn StorageBackend> = match config.storage.backend {
+            StorageBackendKind::Jsonl => Arc::new(JsonlBackend::new(config)),
+            StorageBackendKind::Sqlite => Arc::new(SqliteBackend::connect(&config).await?),
+        };
+
+        Ok(Self { backend })
+    }
+
+    pub async fn initialize(&self) -> Result<()> {
+        self.backend.initialize().await
+    }
+
+    pub async fn append_plan(&self, plan: Plan) -> Result<()> {
+        self.backend.append_plan(plan).await
+    }
+
+    pub async fn append_task(&self, task: Task) -> Result<()> {
+        self.backend.append_task(task).await
+    }
+
+    pub async fn append_context(&self, context: ContextItem) -> Result<()> {
+        self.backend.append_context(context).await
+    }
+
+    pub async fn append_event(&self, event: Event) -> Result<()> {
+        self.backend.append_event(event).await
+    }
+
+    pub async fn append_review(&self, review: Review) -> Result<()> {
+        self.backend.append_review(review).await
+    }
+
+    pub async fn read_plans(&self) -> Result<Vec<Plan>> {
+        self.backend.read_plans().await
+    }
+
+    pub async fn read_tasks(&self) -> Result<Vec<Task>> {
+        self.backend.read_tasks().await
+    }
+
+    pub async fn read_context(&self) -> Result<Vec<ContextItem>> {
+        self.backend.read_context().await
+    }
+
+    pub async fn read_events(&self) -> Result<Vec<Event>> {
+        self.backend.read_events().await
+    }
+
+    pub async fn read_reviews(&self) -> Result<Vec<Review>> {
+        self.backend.read_reviews().await
+    }
+
+    pub async fn plans_count(&self) -> Result<usize> {
+        self.backend.plans_count().await
+    }
+
+    pub async fn tasks_count(&self) -> Result<usize> {
+        self.backend.tasks_count().await
+    }
+
+    pub async fn context_count(&self) -> Result<usize> {
+        self.backend.context_count().await
+    }
+
+    pub async fn events_count(&self) -> Result<usize> {
+        self.backend.events_count().await
+    }
+
+    pub async fn reviews_count(&self) -> Result<usize> {
+        self.backend.reviews_count().await
+    }
+
+    pub async fn read_plans_since(&self, watermark: usize) -> Result<Vec<Plan>> {
+        self.backend.read_plans_since(watermark).await
+    }
+
+    pub async fn read_tasks_since(&self, watermark: usize) -> Result<Vec<Task>> {
+        self.backend.read_tasks_since(watermark).await
+    }
+
+    pub async fn read_context_since(&self, watermark: usize) -> Result<Vec<ContextItem>> {
+        self.backend.read_context_since(watermark).await
+    }
+
+    pub async fn read_events_since(&self, watermark: usize) -> Result<Vec<Event>> {
+        self.backend.read_events_since(watermark).await
+    }
+
+    pub async fn read_reviews_since(&self, watermark: usize) -> Result<Vec<Review>> {
+        self.backend.read_reviews_since(watermark).await
+    }
+
+    pub fn watch_plans(&self) -> Result<flume::Receiver<Plan>> {
+        self.backend.watch_plans()
+    }
+
+    pub fn watch_tasks(&self) -> Result<flume::Receiver<Task>> {
+        self.backend.watch_tasks()
+    }
+
+    pub fn watch_context(&self) -> Result<flume::Receiver<ContextItem>> {
+        self.backend.watch_context()
+    }
+
+    pub fn watch_events(&self) -> Result<flume::Receiver<Event>> {
+        self.backend.watch_events()
+    }
+
+    pub fn watch_reviews(&self) -> Result<flume::Receiver<Review>> {
+        self.backend.watch_reviews()
+    }
+}
@@ -0,0 +1,328 @@
+//! `StorageBackend` implementation backed by SQLite in WAL mode. Unlike the
+//! JSONL backend, writes never take a whole-file exclusive lock and reads
+//! never block behind one, so this is the backend to reach for once a
+//! project's history gets large or multiple agents are writing at once.
+//!
+//! Each entity gets one table keyed by id, storing the record as a JSON
+//! blob next to the timestamp column used for last-writer-wins merges and
+//! a few query concerns. This is deliberately not a normalized, queryable
+//! schema the way `engine::cache`'s tables are — that cache exists to
+//! answer lookups fast; this backend exists to replace the JSONL log it's
+//! populated from, so it only needs to append and replay records.
+
+use super::StorageBackend;
+use crate::config::Config;
+use crate::error::{Result, StorageError};
+use crate::schemas::{ContextItem, Event, Plan, Review, Task};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use sqlx::Row;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// How often a `watch_*` subscription polls for new rows. There's no
+/// equivalent of JSONL's filesystem watch here — WAL writes don't touch the
+/// main db file in a way a watcher can cheaply distinguish from internal
+/// checkpointing — so this backend falls back to polling `read_since`.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Clone)]
+pub struct SqliteBackend {
+    pool: SqlitePool,
+}
+
+impl SqliteBackend {
+    pub async fn connect(config: &Config) -> Result<Self> {
+        let db_path = config.storage_db_path();
+        debug!(path = %db_path.display(), "Opening SQLite storage backend");
+
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| StorageError::from_io(parent, &e))?;
+        }
+
+        let db_url = format!("sqlite://{}?mode=rwc", db_path.display());
+        let pool = SqlitePoolOptions::new().max_connections(8).connect(&db_url).await?;
+
+        let backend = Self { pool };
+        backend.set_pragmas().await?;
+        backend.ensure_schema().await?;
+
+        Ok(backend)
+    }
+
+    async fn set_pragmas(&self) -> Result<()> {
+        sqlx::query("PRAGMA journal_mode = WAL").execute(&self.pool).await?;
+        sqlx::query("PRAGMA synchronous = NORMAL").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    async fn ensure_schema(&self) -> Result<()> {
+        for (table, timestamp_column) in [
+            ("plans", "updated_at"),
+            ("tasks", "updated_at"),
+            ("context_items", "created_at"),
+            ("events", "timestamp"),
+            ("reviews", "updated_at"),
+        ] {
+            sqlx::query(&format!(
+                "CREATE TABLE IF NOT EXISTS {table} (
+                    id TEXT PRIMARY KEY,
+                    {timestamp_column} TEXT NOT NULL,
+                    data TEXT NOT NULL,
+                    seq INTEGER NOT NULL
+                )"
+            ))
+            .execute(&self.pool)
+            .await?;
+
+            sqlx::query(&format!(
+                "CREATE INDEX IF NOT EXISTS idx_{table}_seq ON {table} (seq)"
+            ))
+            .execute(&self.pool)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Inserts `record`, or overwrites the existing row for its id as long
+    /// as `timestamp` isn't older than what's already stored — the same
+    /// last-writer-wins rule `JsonlBackend`'s checkpoint merge uses.
+    ///
+    /// `rowid` never changes on an `ON CONFLICT DO UPDATE`, so it can't
+    /// stand in for an append-order sequence once rows start getting
+    /// updated (which every entity does — see the repeated `append_task`
+    /// calls on every status transition). `seq` is a monotonically
+    /// increasing counter bumped on *every* upsert, insert or update alike,
+    /// so `read_since`/`count` can key off it instead and actually observe
+    /// updates made after a watermark was taken.
+    async fn upsert<T: Serialize>(&self, table: &str, timestamp_column: &str, id: &str, timestamp: &str, record: &T) -> Result<()> {
+        let data = serde_json::to_string(record)?;
+
+        sqlx::query(&format!(
+            "INSERT INTO {table} (id, {timestamp_column}, data, seq)
+             VALUES (?, ?, ?, (SELECT COALESCE(MAX(seq), 0) + 1 FROM {table}))
+             ON CONFLICT(id) DO UPDATE SET
+                {timestamp_column} = excluded.{timestamp_column},
+                data = excluded.data,
+                seq = (SELECT COALESCE(MAX(seq), 0) + 1 FROM {table})
+             WHERE excluded.{timestamp_column} >= {table}.{timestamp_column}"
+        ))
+        .bind(id)
+        .bind(timestamp)
+        .bind(data)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Reads every row of `table` in append order (`seq`, which advances on
+    /// every upsert — including an update to an existing id — unlike `rowid`).
+    async fn read_all<T: DeserializeOwned>(&self, table: &str) -> Result<Vec<T>> {
+        let rows = sqlx::query(&format!("SELECT data FROM {table} ORDER BY seq ASC"))
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| Ok(serde_json::from_str(row.get::<String, _>("data").as_str())?))
+            .collect()
+    }
+
+    /// Reads every row of `table` whose `seq` exceeds `watermark` (a value
+    /// previously obtained from `count`), in append order. Because `seq` is
+    /// bumped on updates too, a row updated after `watermark` was taken is
+    /// returned even though it was originally inserted before it.
+    async fn read_since<T: DeserializeOwned>(&self, table: &str, watermark: usize) -> Result<Vec<T>> {
+        let rows = sqlx::query(&format!(
+            "SELECT data FROM {table} WHERE seq > ? ORDER BY seq ASC"
+        ))
+        .bind(watermark as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| Ok(serde_json::from_str(row.get::<String, _>("data").as_str())?))
+            .collect()
+    }
+
+    /// The current `seq` high-water mark for `table`, suitable as a
+    /// `read_since` watermark. Not a row count (an upsert that only updates
+    /// an existing row doesn't add one), but `StorageBackend`'s contract
+    /// only requires `*_count`/`read_*_since` to agree on units, not that
+    /// `*_count` equals `COUNT(*)`.
+    async fn count(&self, table: &str) -> Result<usize> {
+        let row = sqlx::query(&format!("SELECT COALESCE(MAX(seq), 0) AS seq FROM {table}"))
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.get::<i64, _>("seq") as usize)
+    }
+
+    /// Subscribes to rows inserted into `table` from this point on, by
+    /// polling `read_since` on a background task and advancing its own
+    /// watermark as it forwards each batch.
+    fn watch_table<T>(&self, table: &'static str) -> Result<flume::Receiver<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let (tx, rx) = flume::unbounded();
+        let backend = self.clone();
+
+        tokio::spawn(async move {
+            let mut watermark = match backend.count(table).await {
+                Ok(count) => count,
+                Err(e) => {
+                    warn!(error = %e, table, "Failed to seed watch watermark, giving up");
+                    return;
+                }
+            };
+
+            let mut interval = tokio::time::interval(WATCH_POLL_INTERVAL);
+            loop {
+                interval.tick().await;
+
+                let records: Vec<T> = match backend.read_since(table, watermark).await {
+                    Ok(records) => records,
+                    Err(e) => {
+                        warn!(error = %e, table, "Failed to poll for new rows while watching");
+                        continue;
+                    }
+                };
+
+                if records.is_empty() {
+                    continue;
+                }
+                watermark += records.len();
+
+                for record in records {
+                    if tx.send(record).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+}
+
+#[async_trait]
+impl StorageBackend for SqliteBackend {
+    async fn initialize(&self) -> Result<()> {
+        // Tables are created in `connect`, so there's nothing left to do.
+        Ok(())
+    }
+
+    async fn append_plan(&self, plan: Plan) -> Result<()> {
+        self.upsert("plans", "updated_at", &plan.id, &plan.updated_at.to_rfc3339(), &plan)
+            .await
+    }
+
+    async fn append_task(&self, task: Task) -> Result<()> {
+        self.upsert("tasks", "updated_at", &task.id, &task.updated_at.to_rfc3339(), &task)
+            .await
+    }
+
+    async fn append_context(&self, context: ContextItem) -> Result<()> {
+        self.upsert(
+            "context_items",
+            "created_at",
+            &context.id,
+            &context.created_at.to_rfc3339(),
+            &context,
+        )
+        .await
+    }
+
+    async fn append_event(&self, event: Event) -> Result<()> {
+        self.upsert("events", "timestamp", &event.id, &event.timestamp.to_rfc3339(), &event)
+            .await
+    }
+
+    async fn append_review(&self, review: Review) -> Result<()> {
+        self.upsert("reviews", "updated_at", &review.id, &review.updated_at.to_rfc3339(), &review)
+            .await
+    }
+
+    async fn read_plans(&self) -> Result<Vec<Plan>> {
+        self.read_all("plans").await
+    }
+
+    async fn read_tasks(&self) -> Result<Vec<Task>> {
+        self.read_all("tasks").await
+    }
+
+    async fn read_context(&self) -> Result<Vec<ContextItem>> {
+        self.read_all("context_items").await
+    }
+
+    async fn read_events(&self) -> Result<Vec<Event>> {
+        self.read_all("events").await
+    }
+
+    async fn read_reviews(&self) -> Result<Vec<Review>> {
+        self.read_all("reviews").await
+    }
+
+    async fn plans_count(&self) -> Result<usize> {
+        self.count("plans").await
+    }
+
+    async fn tasks_count(&self) -> Result<usize> {
+        self.count("tasks").await
+    }
+
+    async fn context_count(&self) -> Result<usize> {
+        self.count("context_items").await
+    }
+
+    async fn events_count(&self) -> Result<usize> {
+        self.count("events").await
+    }
+
+    async fn reviews_count(&self) -> Result<usize> {
+        self.count("reviews").await
+    }
+
+    async fn read_plans_since(&self, watermark: usize) -> Result<Vec<Plan>> {
+        self.read_since("plans", watermark).await
+    }
+
+    async fn read_tasks_since(&self, watermark: usize) -> Result<Vec<Task>> {
+        self.read_since("tasks", watermark).await
+    }
+
+    async fn read_context_since(&self, watermark: usize) -> Result<Vec<ContextItem>> {
+        self.read_since("context_items", watermark).await
+    }
+
+    async fn read_events_since(&self, watermark: usize) -> Result<Vec<Event>> {
+        self.read_since("events", watermark).await
+    }
+
+    async fn read_reviews_since(&self, watermark: usize) -> Result<Vec<Review>> {
+        self.read_since("reviews", watermark).await
+    }
+
+    fn watch_plans(&self) -> Result<flume::Receiver<Plan>> {
+        self.watch_table("plans")
+    }
+
+    fn watch_tasks(&self) -> Result<flume::Receiver<Task>> {
+        self.watch_table("tasks")
+    }
+
+    fn watch_context(&self) -> Result<flume::Receiver<ContextItem>> {
+        self.watch_table("context_items")
+    }
+
+    fn watch_events(&self) -> Result<flume::Receiver<Event>> {
+        self.watch_table("events")
+    }
+
+    fn watch_reviews(&self) -> Result<flume::Receiver<Review>> {
+        self.watch_table("reviews")
+    }
+}
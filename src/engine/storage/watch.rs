@@ -0,0 +1,123 @@
+//! Filesystem live-tail for JSONL logs, turning the append-only store into
+//! an event source `CrocEngine` and external tools can subscribe to instead
+//! of polling `read_*`.
+//!
+//! Each call seeds at the current end of the file, then hands the rest off
+//! to a dedicated OS thread that holds a `notify` watcher on the log's
+//! parent directory (watching the directory rather than the file survives
+//! editors/tools that replace the file via rename) and an offset it keeps
+//! seeking forward from. Parsed records cross back to the caller over an
+//! unbounded `flume` channel; once the receiver is dropped, the thread stops
+//! the next time it has a record to forward.
+
+use crate::error::{Result, StorageError};
+use notify::{RecursiveMode, Watcher};
+use serde::de::DeserializeOwned;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// How long to wait after the first filesystem event before reading, so a
+/// burst of writes (e.g. a checkpoint write immediately following the
+/// append that triggered it) is drained in one pass instead of one read
+/// per event.
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Starts tailing `path` for newly-appended JSONL records, returning a
+/// channel that yields each one as it's parsed.
+pub fn watch_jsonl<T>(path: PathBuf) -> Result<flume::Receiver<T>>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let (record_tx, record_rx) = flume::unbounded();
+    let (fs_tx, fs_rx) = std::sync::mpsc::channel();
+
+    let mut watcher =
+        notify::recommended_watcher(move |event| {
+            let _ = fs_tx.send(event);
+        })
+        .map_err(|e| StorageError::Other {
+            message: format!("Failed to create filesystem watcher: {}", e),
+        })?;
+
+    let watch_dir = path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    watcher
+        .watch(&watch_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| StorageError::Other {
+            message: format!("Failed to watch {}: {}", watch_dir.display(), e),
+        })?;
+
+    let mut offset = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+    std::thread::spawn(move || {
+        // Keeping the watcher alive for the thread's lifetime is the whole
+        // point of moving it in; it's never read again.
+        let _watcher = watcher;
+        let mut buffer = String::new();
+
+        while let Ok(event) = fs_rx.recv() {
+            match event {
+                Ok(event) if event.paths.iter().any(|p| p == &path) => {}
+                Ok(_) => continue,
+                Err(e) => {
+                    warn!(error = %e, "Filesystem watch error while tailing {}", path.display());
+                    continue;
+                }
+            }
+
+            // Drain whatever else queued up during the debounce window so a
+            // burst of writes is processed once instead of once per event.
+            std::thread::sleep(DEBOUNCE);
+            while fs_rx.try_recv().is_ok() {}
+
+            let Ok(len) = std::fs::metadata(&path).map(|m| m.len()) else {
+                continue;
+            };
+
+            if len < offset {
+                debug!(path = %path.display(), "Log truncated, resetting offset and re-reading from start");
+                offset = 0;
+                buffer.clear();
+            }
+
+            let Ok(mut file) = std::fs::File::open(&path) else {
+                continue;
+            };
+            if file.seek(SeekFrom::Start(offset)).is_err() {
+                continue;
+            }
+
+            let mut chunk = String::new();
+            if file.read_to_string(&mut chunk).is_err() {
+                // Likely a non-UTF8 partial write racing the reader; try
+                // again on the next event rather than losing the offset.
+                continue;
+            }
+            offset = len;
+            buffer.push_str(&chunk);
+
+            while let Some(newline) = buffer.find('\n') {
+                let line: String = buffer.drain(..=newline).collect();
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+
+                match serde_json::from_str::<T>(line) {
+                    Ok(record) => {
+                        if record_tx.send(record).is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => warn!(error = %e, line, "Skipping unparsable line while tailing"),
+                }
+            }
+        }
+    });
+
+    Ok(record_rx)
+}
@@ -0,0 +1,232 @@
+use crate::engine::jobserver::JobServer;
+use crate::engine::CrocEngine;
+use crate::error::{CrocError, Result};
+use crate::schemas::{Event, EventType, LogStream, Role};
+use crate::tmux::{TmuxSession, find_croc_sessions};
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Pane content that indicates a worker finished its subtask cleanly.
+const COMPLETION_SENTINEL: &str = "---END_CROC_STATUS---";
+
+/// Pane content that indicates a worker hit an unrecoverable error.
+const FAILURE_SENTINEL: &str = "CROC_TASK_FAILED";
+
+/// Polls `croc-worker-*`/`croc-foreman-*` tmux sessions and turns pane
+/// output changes into `Event`s, so nobody has to babysit tmux by hand.
+pub struct Supervisor {
+    poll_interval: Duration,
+    retries: u32,
+    retry_backoff: Duration,
+    snapshots: HashMap<String, String>,
+}
+
+impl Supervisor {
+    pub fn new(poll_interval: Duration) -> Self {
+        Self {
+            poll_interval,
+            retries: 3,
+            retry_backoff: Duration::from_millis(500),
+            snapshots: HashMap::new(),
+        }
+    }
+
+    /// Runs the poll loop forever, appending events to `engine` as sessions change.
+    /// Intended to be spawned as its own task; callers cancel it by dropping the task.
+    pub async fn run(&mut self, engine: &CrocEngine) -> Result<()> {
+        loop {
+            self.tick(engine).await?;
+            tokio::time::sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Runs a single poll pass over the current set of croc sessions.
+    pub async fn tick(&mut self, engine: &CrocEngine) -> Result<()> {
+        let sessions = retry(self.retries, self.retry_backoff, find_croc_sessions)?;
+
+        for name in sessions {
+            let (task_id, plan_id) = match parse_session_name(&name) {
+                Some(ids) => ids,
+                None => continue,
+            };
+
+            let session = TmuxSession::new(name.clone());
+            let still_exists = retry(self.retries, self.retry_backoff, || session.exists())?;
+
+            if !still_exists {
+                self.snapshots.remove(&name);
+                let event = Event::new(EventType::WorkerComplete)
+                    .with_plan(plan_id)
+                    .with_task(task_id);
+                engine.append_event(&event).await?;
+                self.release_token(engine)?;
+                continue;
+            }
+
+            let pane = retry(self.retries, self.retry_backoff, || session.capture_pane())?;
+            let previous = self.snapshots.get(&name).cloned().unwrap_or_default();
+
+            if pane == previous {
+                continue;
+            }
+
+            let role = session_role(&name);
+            for line in new_lines(&previous, &pane) {
+                engine.append_log(&task_id, role, LogStream::Stdout, line).await?;
+            }
+
+            if pane.contains(FAILURE_SENTINEL) {
+                let last_error = serde_json::json!({ "tail": tail(&pane) });
+                let event = Event::new(EventType::WorkerFailed)
+                    .with_plan(plan_id.clone())
+                    .with_task(task_id.clone())
+                    .with_data(last_error.clone());
+                engine.append_event(&event).await?;
+                self.mark_task_failed(engine, &task_id, last_error).await?;
+                self.release_token(engine)?;
+            } else if pane.contains(COMPLETION_SENTINEL) {
+                if role == Role::Worker {
+                    engine.ingest_worker_status(&task_id, &pane).await?;
+                }
+
+                let event = Event::new(EventType::WorkerComplete)
+                    .with_plan(plan_id)
+                    .with_task(task_id);
+                engine.append_event(&event).await?;
+                self.release_token(engine)?;
+            } else {
+                let event = Event::new(EventType::WorkerProgress)
+                    .with_plan(plan_id)
+                    .with_task(task_id)
+                    .with_data(serde_json::json!({ "tail": tail(&pane) }));
+                engine.append_event(&event).await?;
+            }
+
+            self.snapshots.insert(name, pane);
+        }
+
+        Ok(())
+    }
+
+    async fn mark_task_failed(
+        &self,
+        engine: &CrocEngine,
+        task_id: &str,
+        last_error: serde_json::Value,
+    ) -> Result<()> {
+        if let Some(mut task) = engine.get_task_opt(task_id).await? {
+            task.status = crate::schemas::TaskStatus::Failed;
+            task.last_error = Some(last_error);
+            engine.append_task(&task).await?;
+        }
+        Ok(())
+    }
+
+    fn release_token(&self, engine: &CrocEngine) -> Result<()> {
+        let jobserver = JobServer::new(
+            engine.config().jobserver_path(),
+            engine.config().max_parallel_workers(),
+        )?;
+        jobserver.release_token()
+    }
+}
+
+/// Lines present in `pane` but not yet in `previous`, assuming tmux's
+/// scrollback only ever grows by appending (true as long as the pane
+/// doesn't scroll past its history limit between polls).
+fn new_lines<'a>(previous: &str, pane: &'a str) -> Vec<&'a str> {
+    let previous_count = previous.lines().count();
+    pane.lines().skip(previous_count).collect()
+}
+
+/// Infers the role a `croc-worker-*`/`croc-foreman-*` session was spawned for.
+fn session_role(name: &str) -> Role {
+    if name.starts_with("croc-foreman-") {
+        Role::Foreman
+    } else {
+        Role::Worker
+    }
+}
+
+fn tail(pane: &str) -> String {
+    const MAX_LINES: usize = 20;
+    let lines: Vec<&str> = pane.lines().collect();
+    let start = lines.len().saturating_sub(MAX_LINES);
+    lines[start..].join("\n")
+}
+
+/// Extracts `(task_id, plan_id)` from a `croc-worker-<plan>-<task>` or
+/// `croc-foreman-<plan>` session name. Returns `None` for unrecognized sessions.
+fn parse_session_name(name: &str) -> Option<(String, String)> {
+    if let Some(rest) = name.strip_prefix("croc-worker-") {
+        let mut parts = rest.splitn(2, '-');
+        let plan = parts.next()?;
+        let task = parts.next()?;
+        return Some((format!("task-{}.{}", plan, task), format!("plan-{}", plan)));
+    }
+
+    if let Some(plan) = name.strip_prefix("croc-foreman-") {
+        return Some((format!("task-{}", plan), format!("plan-{}", plan)));
+    }
+
+    None
+}
+
+/// Retries a tmux call up to `attempts` times with a fixed backoff before
+/// giving up, so a single dropped tmux invocation doesn't kill the watcher.
+fn retry<T>(attempts: u32, backoff: Duration, mut f: impl FnMut() -> Result<T>) -> Result<T> {
+    let mut last_err = None;
+
+    for attempt in 0..attempts.max(1) {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                warn!(attempt, error = %err, "tmux call failed, retrying");
+                last_err = Some(err);
+                std::thread::sleep(backoff);
+            }
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| {
+        CrocError::tmux("retry", "-", None, "retry loop exited without a result")
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_worker_session_name() {
+        assert_eq!(
+            parse_session_name("croc-worker-abc123-1"),
+            Some(("task-abc123.1".to_string(), "plan-abc123".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_foreman_session_name() {
+        assert_eq!(
+            parse_session_name("croc-foreman-abc123"),
+            Some(("task-abc123".to_string(), "plan-abc123".to_string()))
+        );
+    }
+
+    #[test]
+    fn ignores_unrelated_session_name() {
+        assert_eq!(parse_session_name("some-other-session"), None);
+    }
+
+    #[test]
+    fn tail_keeps_only_last_lines() {
+        let pane = (1..=30)
+            .map(|n| format!("line {}", n))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let result = tail(&pane);
+        assert_eq!(result.lines().count(), 20);
+        assert!(result.ends_with("line 30"));
+    }
+}
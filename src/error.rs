@@ -1,7 +1,12 @@
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+/// Every variant is `Serialize`/`Deserialize` so a failure can be recorded
+/// verbatim as the `data` payload of a `WorkerFailed` `Event`, turning
+/// `events.jsonl` into a self-describing failure log instead of a place
+/// where console strings go to die.
+#[derive(Debug, Clone, Error, Serialize, Deserialize)]
 pub enum CrocError {
     #[error("Project already initialized at {path}")]
     AlreadyInitialized { path: PathBuf },
@@ -15,14 +20,34 @@ pub enum CrocError {
     #[error("Invalid configuration: {reason}")]
     InvalidConfig { reason: String },
 
-    #[error("Storage error: {message}")]
-    Storage { message: String },
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
 
     #[error("Cache error: {message}")]
     Cache { message: String },
 
-    #[error("Tmux error: {message}")]
-    Tmux { message: String },
+    #[error("Log sink error: {message}")]
+    Log { message: String },
+
+    #[error("tmux {op} failed for session '{session}' (exit {exit_code:?}): {stderr_tail}")]
+    Tmux {
+        op: String,
+        session: String,
+        exit_code: Option<i32>,
+        stderr_tail: String,
+    },
+
+    #[error("Git worktree error for '{worktree}': {message}")]
+    Worktree { worktree: String, message: String },
+
+    #[error("Jobserver error: {message}")]
+    Jobserver { message: String },
+
+    #[error("Notifier error: {message}")]
+    Notifier { message: String },
+
+    #[error("Hook error: {message}")]
+    Hook { message: String },
 
     #[error("Entity not found: {entity_type} with id '{id}'")]
     NotFound { entity_type: String, id: String },
@@ -30,17 +55,129 @@ pub enum CrocError {
     #[error("Invalid role: {role}")]
     InvalidRole { role: String },
 
+    #[error("Dependency cycle detected in plan '{plan_id}' among tasks: {tasks:?}")]
+    DependencyCycle { plan_id: String, tasks: Vec<String> },
+
+    #[error("Scheduler exhausted for plan '{plan_id}': no ready tasks but {remaining:?} are incomplete")]
+    SchedulerExhausted { plan_id: String, remaining: Vec<String> },
+
     #[error("Missing environment variable: {name}")]
     MissingEnvVar { name: String },
 
     #[error("IO error: {0}")]
-    Io(#[from] std::io::Error),
+    Io(String),
 
     #[error("JSON error: {0}")]
-    Json(#[from] serde_json::Error),
+    Json(String),
 
     #[error("SQLite error: {0}")]
-    Sqlite(#[from] sqlx::Error),
+    Sqlite(String),
+}
+
+/// A classified storage-backend failure, so callers can branch on `class()`
+/// (or match the variant directly) instead of pattern-matching a message
+/// string — e.g. retrying `LockBusy` but surfacing `Corrupt` straight to the
+/// user. `JsonlBackend` and `SqliteBackend` build these from the
+/// `std::io::ErrorKind` they observe wherever one applies; anything that
+/// doesn't fit a structured variant falls back to `Other`.
+#[derive(Debug, Clone, Error, Serialize, Deserialize)]
+pub enum StorageError {
+    #[error("Not found: {path}")]
+    NotFound { path: PathBuf },
+
+    #[error("Permission denied: {path}")]
+    PermissionDenied { path: PathBuf },
+
+    #[error("Lock busy on {path}")]
+    LockBusy { path: PathBuf },
+
+    #[error("Corrupt record in {path} at line {line}: {source}")]
+    Corrupt {
+        path: PathBuf,
+        line: usize,
+        source: String,
+    },
+
+    #[error("IO error ({kind}) on {path}")]
+    Io { kind: String, path: PathBuf },
+
+    #[error("{message}")]
+    Other { message: String },
+}
+
+impl StorageError {
+    /// Stable category string for each variant, suitable for logging or
+    /// metrics where the `Display` text (which embeds the path) would be
+    /// too high-cardinality.
+    pub fn class(&self) -> &'static str {
+        match self {
+            StorageError::NotFound { .. } => "not_found",
+            StorageError::PermissionDenied { .. } => "permission_denied",
+            StorageError::LockBusy { .. } => "lock_busy",
+            StorageError::Corrupt { .. } => "corrupt",
+            StorageError::Io { .. } => "io",
+            StorageError::Other { .. } => "other",
+        }
+    }
+
+    /// Classifies an `io::Error` encountered while operating on `path`,
+    /// picking the matching structured variant where one applies and
+    /// falling back to `Io` (keyed by `ErrorKind`'s `Debug` form, since
+    /// `ErrorKind` isn't `Serialize`) otherwise.
+    pub fn from_io(path: impl Into<PathBuf>, err: &std::io::Error) -> Self {
+        let path = path.into();
+        match err.kind() {
+            std::io::ErrorKind::NotFound => StorageError::NotFound { path },
+            std::io::ErrorKind::PermissionDenied => StorageError::PermissionDenied { path },
+            std::io::ErrorKind::WouldBlock => StorageError::LockBusy { path },
+            kind => StorageError::Io {
+                kind: format!("{:?}", kind),
+                path,
+            },
+        }
+    }
+}
+
+impl CrocError {
+    pub fn tmux(op: impl Into<String>, session: impl Into<String>, exit_code: Option<i32>, stderr: impl Into<String>) -> Self {
+        let stderr = stderr.into();
+        // rev().take(5) grabs the last 5 lines but leaves them most-recent-
+        // first; reverse back so stderr_tail reads in original chronological
+        // order, like the rest of events.jsonl's failure log.
+        let stderr_tail = stderr
+            .lines()
+            .rev()
+            .take(5)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .collect::<Vec<_>>()
+            .join("\n");
+        Self::Tmux {
+            op: op.into(),
+            session: session.into(),
+            exit_code,
+            stderr_tail,
+        }
+    }
+}
+
+impl From<std::io::Error> for CrocError {
+    fn from(err: std::io::Error) -> Self {
+        CrocError::Io(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for CrocError {
+    fn from(err: serde_json::Error) -> Self {
+        CrocError::Json(err.to_string())
+    }
+}
+
+impl From<sqlx::Error> for CrocError {
+    fn from(err: sqlx::Error) -> Self {
+        CrocError::Sqlite(err.to_string())
+    }
 }
 
 pub type Result<T> = std::result::Result<T, CrocError>;
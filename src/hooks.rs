@@ -0,0 +1,114 @@
+//! Loads `.croc/hooks.lua`, a user-supplied Lua script that can customize
+//! the four role prompts and gate scheduler/ingestion transitions without
+//! recompiling `croc`. Absent a hooks file, every callback is a no-op and
+//! the static prompts in `commands::prime` behave exactly as before.
+
+use crate::error::{CrocError, Result};
+use crate::schemas::Task;
+use crate::status::WorkerStatus;
+use mlua::{Lua, LuaSerdeExt};
+use std::path::Path;
+
+/// A loaded `.croc/hooks.lua`. Each callback is optional; a script that
+/// only defines `can_spawn` leaves `pre_prime`/`on_worker_status` as no-ops.
+pub struct HookEngine {
+    lua: Lua,
+}
+
+impl HookEngine {
+    /// Loads `path` if it exists, returning `None` if there is no hooks
+    /// file (the common case) so callers can skip the hook pipeline entirely.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let source = std::fs::read_to_string(path)?;
+        let lua = Lua::new();
+        lua.load(&source).exec().map_err(|e| CrocError::Hook {
+            message: format!("Failed to load {}: {}", path.display(), e),
+        })?;
+
+        Ok(Some(Self { lua }))
+    }
+
+    /// Runs `pre_prime(role, ctx)` if defined, and appends its returned
+    /// string (if any) to the generated prompt after a blank line. The
+    /// generated prompt is passed to the hook as `ctx.base_prompt`; the
+    /// return value is always appended, so a hook cannot replace or remove
+    /// any part of the generated prompt.
+    pub fn pre_prime(&self, role: &str, ctx: serde_json::Value, prompt: &str) -> Result<String> {
+        let Some(callback) = self.get_function("pre_prime")? else {
+            return Ok(prompt.to_string());
+        };
+
+        let mut ctx = ctx;
+        if let serde_json::Value::Object(ref mut map) = ctx {
+            map.insert("base_prompt".to_string(), serde_json::Value::String(prompt.to_string()));
+        }
+
+        let lua_ctx = self
+            .lua
+            .to_value(&ctx)
+            .map_err(|e| CrocError::Hook { message: e.to_string() })?;
+
+        let extra: Option<String> = callback
+            .call((role.to_string(), lua_ctx))
+            .map_err(|e| CrocError::Hook {
+                message: format!("pre_prime failed: {}", e),
+            })?;
+
+        match extra {
+            Some(extra) if !extra.is_empty() => Ok(format!("{}\n\n{}", prompt, extra)),
+            _ => Ok(prompt.to_string()),
+        }
+    }
+
+    /// Runs `on_worker_status(status)` if defined; defaults to accepting
+    /// the completion when no hook overrides it.
+    pub fn on_worker_status(&self, status: &WorkerStatus) -> Result<bool> {
+        let Some(callback) = self.get_function("on_worker_status")? else {
+            return Ok(true);
+        };
+
+        let lua_status = self
+            .lua
+            .to_value(status)
+            .map_err(|e| CrocError::Hook { message: e.to_string() })?;
+
+        callback
+            .call(lua_status)
+            .map_err(|e| CrocError::Hook {
+                message: format!("on_worker_status failed: {}", e),
+            })
+    }
+
+    /// Runs `can_spawn(task)` if defined; defaults to allowing the spawn.
+    pub fn can_spawn(&self, task: &Task) -> Result<bool> {
+        let Some(callback) = self.get_function("can_spawn")? else {
+            return Ok(true);
+        };
+
+        let lua_task = self
+            .lua
+            .to_value(task)
+            .map_err(|e| CrocError::Hook { message: e.to_string() })?;
+
+        callback
+            .call(lua_task)
+            .map_err(|e| CrocError::Hook {
+                message: format!("can_spawn failed: {}", e),
+            })
+    }
+
+    fn get_function(&self, name: &str) -> Result<Option<mlua::Function>> {
+        let globals = self.lua.globals();
+        match globals.get::<_, mlua::Value>(name) {
+            Ok(mlua::Value::Function(f)) => Ok(Some(f)),
+            Ok(_) => Ok(None),
+            Err(e) => Err(CrocError::Hook {
+                message: format!("Failed to look up {}: {}", name, e),
+            }),
+        }
+    }
+}
@@ -1,10 +1,15 @@
 pub mod cli;
 pub mod commands;
 pub mod config;
+pub mod daemon;
 pub mod engine;
 pub mod error;
+pub mod hooks;
 pub mod logging;
+pub mod review;
+pub mod rpc;
 pub mod schemas;
+pub mod status;
 pub mod tmux;
 
 pub use cli::App;
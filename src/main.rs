@@ -8,15 +8,22 @@ async fn main() -> anyhow::Result<()> {
     color_eyre::install().ok();
 
     let app = App::parse();
+    let config_override = app.global.config.clone();
 
-    let config = Config::from_current_dir().ok();
+    let config = Config::from_current_dir()
+        .and_then(|c| c.with_override(config_override.as_deref()))
+        .ok();
     let logs_dir = config.as_ref().map(|c| c.logs_dir());
 
     let _log_guard = crocodile::logging::init(app.global.verbose, logs_dir.as_deref())?;
 
     match app.command {
-        Some(Command::Init(args)) => commands::init_exec(args).await,
-        Some(Command::Prime(args)) => commands::prime_exec(args).await,
+        Some(Command::Init(args)) => commands::init_exec(args, config_override).await,
+        Some(Command::Prime(args)) => commands::prime_exec(args, config_override).await,
+        Some(Command::Cache(args)) => commands::cache_exec(args, config_override).await,
+        Some(Command::Report(args)) => commands::report_exec(args, config_override).await,
+        Some(Command::Rpc(args)) => commands::rpc_exec(args, config_override).await,
+        Some(Command::Daemon(args)) => commands::daemon_exec(args, config_override).await,
         None => {
             App::parse_from(["croc", "--help"]);
             Ok(())
@@ -0,0 +1,176 @@
+//! Validates the git commits produced for a plan against the Conventional
+//! Commits grammar (`type(scope)!: description`), feeding the results into
+//! the Reviewer prompt's "## Commit Compliance" section as minor-severity
+//! `ReviewIssue`s instead of leaving "Verify plan adherence" unchecked.
+
+use crate::error::{CrocError, Result, StorageError};
+use crate::status::ReviewIssue;
+use std::process::Command;
+use tracing::debug;
+
+const RECOGNIZED_TYPES: &[&str] = &["feat", "fix", "docs", "refactor", "test", "chore"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitCheck {
+    pub subject: String,
+    pub compliant: bool,
+    pub reason: Option<String>,
+}
+
+/// Checks `subject` against `type(scope)!: description`, where `type` is
+/// one of `RECOGNIZED_TYPES`, `(scope)` is optional, and a trailing `!`
+/// before the colon marks a breaking change.
+pub fn validate_commit(subject: &str) -> CommitCheck {
+    let reason = validate(subject).err();
+    CommitCheck {
+        subject: subject.to_string(),
+        compliant: reason.is_none(),
+        reason,
+    }
+}
+
+fn validate(subject: &str) -> std::result::Result<(), String> {
+    let Some((header, description)) = subject.split_once(": ") else {
+        return Err("missing ': <description>' separator".to_string());
+    };
+
+    if description.trim().is_empty() {
+        return Err("empty description".to_string());
+    }
+
+    let header = header.strip_suffix('!').unwrap_or(header);
+
+    let (type_part, scope_part) = match header.split_once('(') {
+        Some((t, rest)) => {
+            let Some(scope) = rest.strip_suffix(')') else {
+                return Err(format!("unterminated scope in '{}'", header));
+            };
+            (t, Some(scope))
+        }
+        None => (header, None),
+    };
+
+    if !RECOGNIZED_TYPES.contains(&type_part) {
+        return Err(format!(
+            "unrecognized type '{}' (expected one of: {})",
+            type_part,
+            RECOGNIZED_TYPES.join(", ")
+        ));
+    }
+
+    if let Some(scope) = scope_part {
+        if scope.is_empty() {
+            return Err("empty scope".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Commit subject lines found in `worktree`'s branch, oldest first.
+pub fn commit_subjects(worktree: &str) -> Result<Vec<String>> {
+    debug!(worktree, "Gathering commit subjects for conventional-commit check");
+
+    let output = Command::new("git")
+        .args(["-C", worktree, "log", "--format=%s", "--reverse"])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("not a git repository") {
+            return Err(CrocError::NotGitRepo {
+                path: worktree.into(),
+            });
+        }
+        return Err(StorageError::Other {
+            message: format!("git log failed in {}: {}", worktree, stderr),
+        }
+        .into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Renders the "## Commit Compliance" section for the Reviewer prompt,
+/// listing only the commits that failed validation.
+pub fn build_commit_compliance_section(commits: &[String]) -> String {
+    let checks: Vec<CommitCheck> = commits.iter().map(|c| validate_commit(c)).collect();
+    let non_conforming: Vec<&CommitCheck> = checks.iter().filter(|c| !c.compliant).collect();
+
+    if commits.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from("\n## Commit Compliance\n\n");
+
+    if non_conforming.is_empty() {
+        section.push_str("All commits follow the Conventional Commits grammar.\n");
+    } else {
+        section.push_str("The following commits do not follow `type(scope): description`:\n\n");
+        for check in &non_conforming {
+            section.push_str(&format!(
+                "- \"{}\" - {}\n",
+                check.subject,
+                check.reason.as_deref().unwrap_or("non-conforming")
+            ));
+        }
+    }
+
+    section
+}
+
+/// Converts non-conforming commits into minor-severity `ReviewIssue`s.
+pub fn commit_issues(commits: &[String]) -> Vec<ReviewIssue> {
+    commits
+        .iter()
+        .map(|c| validate_commit(c))
+        .filter(|c| !c.compliant)
+        .map(|c| ReviewIssue {
+            severity: "minor".to_string(),
+            description: format!(
+                "Commit \"{}\" does not follow Conventional Commits: {}",
+                c.subject,
+                c.reason.unwrap_or_default()
+            ),
+            location: None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_a_conforming_commit() {
+        let check = validate_commit("feat(cli): add report command");
+        assert!(check.compliant);
+    }
+
+    #[test]
+    fn accepts_a_breaking_change_marker() {
+        let check = validate_commit("fix(cache)!: drop the legacy schema");
+        assert!(check.compliant);
+    }
+
+    #[test]
+    fn rejects_an_unrecognized_type() {
+        let check = validate_commit("wip: still figuring this out");
+        assert!(!check.compliant);
+    }
+
+    #[test]
+    fn rejects_a_missing_description() {
+        let check = validate_commit("feat(cli):");
+        assert!(!check.compliant);
+    }
+
+    #[test]
+    fn rejects_a_message_with_no_colon() {
+        let check = validate_commit("added a new feature");
+        assert!(!check.compliant);
+    }
+}
@@ -0,0 +1,323 @@
+//! A JSON-RPC 2.0 control surface over a Unix socket, mirroring the
+//! `CrocEngine` query/mutation methods so an editor plugin or worker agent
+//! can drive plan state without shelling out to `croc prime`. Requests and
+//! responses are newline-delimited JSON objects, one per line, matching
+//! the JSONL convention used everywhere else in this crate.
+
+use crate::engine::CrocEngine;
+use crate::error::CrocError;
+use crate::schemas::{ContextItem, ContextType, Task, TaskStatus};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixListener;
+use tracing::{debug, error, warn};
+
+#[derive(Debug, Deserialize)]
+struct RpcRequest {
+    #[serde(default)]
+    jsonrpc: Option<String>,
+    method: String,
+    #[serde(default)]
+    params: serde_json::Value,
+    #[serde(default)]
+    id: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcErrorBody>,
+    id: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct RpcErrorBody {
+    code: i64,
+    message: String,
+}
+
+/// Serves the JSON-RPC control surface on `socket_path` until the process
+/// is killed. Removes a stale socket file left over from a prior run.
+pub async fn serve(engine: Arc<CrocEngine>, socket_path: &Path) -> crate::error::Result<()> {
+    if socket_path.exists() {
+        std::fs::remove_file(socket_path)?;
+    }
+
+    let listener = UnixListener::bind(socket_path).map_err(|e| CrocError::InvalidConfig {
+        reason: format!("Failed to bind RPC socket {}: {}", socket_path.display(), e),
+    })?;
+
+    debug!(path = %socket_path.display(), "Listening for JSON-RPC connections");
+
+    loop {
+        let (stream, _addr) = listener.accept().await.map_err(std::io::Error::from)?;
+        let engine = Arc::clone(&engine);
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, engine).await {
+                warn!(error = %e, "RPC connection ended with an error");
+            }
+        });
+    }
+}
+
+async fn handle_connection(
+    stream: tokio::net::UnixStream,
+    engine: Arc<CrocEngine>,
+) -> crate::error::Result<()> {
+    let (read_half, mut write_half) = stream.into_split();
+    let mut lines = BufReader::new(read_half).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<RpcRequest>(&line) {
+            Ok(request) => dispatch(&engine, request).await,
+            Err(e) => RpcResponse {
+                jsonrpc: "2.0",
+                result: None,
+                error: Some(RpcErrorBody {
+                    code: -32700,
+                    message: format!("Parse error: {}", e),
+                }),
+                id: serde_json::Value::Null,
+            },
+        };
+
+        let mut payload = serde_json::to_string(&response)?;
+        payload.push('\n');
+        write_half.write_all(payload.as_bytes()).await?;
+    }
+
+    Ok(())
+}
+
+async fn dispatch(engine: &CrocEngine, request: RpcRequest) -> RpcResponse {
+    let id = request.id;
+
+    if let Some(version) = &request.jsonrpc {
+        if version != "2.0" {
+            return RpcResponse {
+                jsonrpc: "2.0",
+                result: None,
+                error: Some(RpcErrorBody {
+                    code: -32600,
+                    message: format!("Unsupported jsonrpc version: {}", version),
+                }),
+                id,
+            };
+        }
+    }
+
+    let result = call_method(engine, &request.method, request.params).await;
+
+    match result {
+        Ok(value) => RpcResponse {
+            jsonrpc: "2.0",
+            result: Some(value),
+            error: None,
+            id,
+        },
+        Err(err) => {
+            error!(method = %request.method, error = %err, "RPC method failed");
+            RpcResponse {
+                jsonrpc: "2.0",
+                result: None,
+                error: Some(RpcErrorBody {
+                    code: error_code(&err),
+                    message: err.to_string(),
+                }),
+                id,
+            }
+        }
+    }
+}
+
+async fn call_method(
+    engine: &CrocEngine,
+    method: &str,
+    params: serde_json::Value,
+) -> crate::error::Result<serde_json::Value> {
+    match method {
+        "plan.get" => {
+            let plan_id = param_str(&params, "plan_id")?;
+            Ok(serde_json::to_value(engine.get_plan(&plan_id).await?)?)
+        }
+        "plan.tasks" => {
+            let plan_id = param_str(&params, "plan_id")?;
+            Ok(serde_json::to_value(
+                engine.get_tasks_for_plan(&plan_id).await?,
+            )?)
+        }
+        "task.get" => {
+            let task_id = param_str(&params, "task_id")?;
+            Ok(serde_json::to_value(engine.get_task(&task_id).await?)?)
+        }
+        "context.for_plan" => {
+            let plan_id = param_str(&params, "plan_id")?;
+            Ok(serde_json::to_value(
+                engine.get_context_for_plan(&plan_id).await?,
+            )?)
+        }
+        "context.for_task" => {
+            let task_id = param_str(&params, "task_id")?;
+            Ok(serde_json::to_value(
+                engine.get_context_for_task(&task_id).await?,
+            )?)
+        }
+        "task.create" => task_create(engine, params).await,
+        "task.update_status" => task_update_status(engine, params).await,
+        "context.add" => context_add(engine, params).await,
+        other => Err(CrocError::InvalidConfig {
+            reason: format!("Unknown RPC method: {}", other),
+        }),
+    }
+}
+
+async fn task_create(
+    engine: &CrocEngine,
+    params: serde_json::Value,
+) -> crate::error::Result<serde_json::Value> {
+    let plan_id = param_str(&params, "plan_id")?;
+    let parent_id = param_str(&params, "parent_id")?;
+    validate_id("plan_id", &plan_id)?;
+    validate_id("parent_id", &parent_id)?;
+    let subtask_num = params
+        .get("subtask_num")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| CrocError::InvalidConfig {
+            reason: "Missing param: subtask_num".to_string(),
+        })? as u32;
+    let title = param_str(&params, "title")?;
+    validate_text("title", &title)?;
+
+    let task = Task::new_subtask(plan_id, parent_id, subtask_num, title);
+    engine.append_task(&task).await?;
+    Ok(serde_json::to_value(task)?)
+}
+
+async fn task_update_status(
+    engine: &CrocEngine,
+    params: serde_json::Value,
+) -> crate::error::Result<serde_json::Value> {
+    let task_id = param_str(&params, "task_id")?;
+    validate_id("task_id", &task_id)?;
+    let status_str = param_str(&params, "status")?;
+    let status: TaskStatus = serde_json::from_str(&format!("\"{}\"", status_str))?;
+
+    let mut task = engine.get_task(&task_id).await?;
+    task.status = status;
+    task.updated_at = chrono::Utc::now();
+    engine.append_task(&task).await?;
+    Ok(serde_json::to_value(task)?)
+}
+
+async fn context_add(
+    engine: &CrocEngine,
+    params: serde_json::Value,
+) -> crate::error::Result<serde_json::Value> {
+    let plan_id = param_str(&params, "plan_id")?;
+    validate_id("plan_id", &plan_id)?;
+    let subtask_id = params
+        .get("subtask_id")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    if let Some(subtask_id) = &subtask_id {
+        validate_id("subtask_id", subtask_id)?;
+    }
+    let item_type_str = param_str(&params, "item_type")?;
+    let content = param_str(&params, "content")?;
+
+    let item_type: ContextType = serde_json::from_str(&format!("\"{}\"", item_type_str))?;
+    let item = match item_type {
+        ContextType::Fact => {
+            let source = params
+                .get("source")
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+            let confidence = params.get("confidence").and_then(|v| v.as_f64()).map(|c| c as f32);
+            ContextItem::new_fact(plan_id, subtask_id, content, source, confidence)
+        }
+        ContextType::Decision => {
+            let reasoning = param_str(&params, "reasoning")?;
+            let alternatives = params.get("alternatives").and_then(|v| {
+                v.as_array().map(|a| {
+                    a.iter()
+                        .filter_map(|x| x.as_str().map(str::to_string))
+                        .collect()
+                })
+            });
+            ContextItem::new_decision(plan_id, subtask_id, content, reasoning, alternatives)
+        }
+    };
+
+    engine.append_context(&item).await?;
+    Ok(serde_json::to_value(item)?)
+}
+
+/// `plan_id`/`task_id`/`parent_id`/`subtask_id` end up interpolated into
+/// `Task::new_subtask`'s generated id (and from there into tmux session
+/// names and `croc`'s own command-line args), so restrict them to a charset
+/// that can never be mistaken for a shell metacharacter or path separator.
+fn validate_id(field: &str, value: &str) -> crate::error::Result<()> {
+    let valid = !value.is_empty()
+        && value.len() <= 128
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+
+    if !valid {
+        return Err(CrocError::InvalidConfig {
+            reason: format!(
+                "Invalid {}: must be 1-128 characters of [a-zA-Z0-9._-]",
+                field
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// Looser than `validate_id`: free text is allowed, but control characters
+/// (which have no business in a title and would corrupt single-line JSONL
+/// records or terminal rendering) are rejected.
+fn validate_text(field: &str, value: &str) -> crate::error::Result<()> {
+    if value.is_empty() || value.len() > 4096 || value.chars().any(|c| c.is_control()) {
+        return Err(CrocError::InvalidConfig {
+            reason: format!("Invalid {}: must be non-empty, <=4096 chars, and control-character-free", field),
+        });
+    }
+
+    Ok(())
+}
+
+fn param_str(params: &serde_json::Value, key: &str) -> crate::error::Result<String> {
+    params
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .ok_or_else(|| CrocError::InvalidConfig {
+            reason: format!("Missing param: {}", key),
+        })
+}
+
+/// Maps a `CrocError` onto a JSON-RPC error code: the reserved `-32602`
+/// (invalid params) and `-32603` (internal error) codes, plus app-specific
+/// codes just below `-32000`. Parse errors (`-32700`) and unsupported
+/// `jsonrpc` versions (`-32600`) are answered before dispatch; an unknown
+/// method arrives as `InvalidConfig` and is reported as `-32602`.
+fn error_code(err: &CrocError) -> i64 {
+    match err {
+        CrocError::NotFound { .. } => -32001,
+        CrocError::InvalidConfig { .. } | CrocError::InvalidRole { .. } => -32602,
+        CrocError::DependencyCycle { .. } | CrocError::SchedulerExhausted { .. } => -32010,
+        CrocError::MissingEnvVar { .. } => -32602,
+        _ => -32603,
+    }
+}
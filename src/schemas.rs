@@ -25,6 +25,9 @@ pub enum TaskStatus {
     Running,
     Complete,
     Failed,
+    /// Unreachable because a dependency transitively failed or is missing,
+    /// or reported blocked by its worker; the scheduler never spawns it.
+    Blocked,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -50,6 +53,7 @@ pub enum EventType {
     ReviewChangesRequested,
     PlanComplete,
     PlanCancelled,
+    WorkerBlocked,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -76,6 +80,14 @@ pub enum Role {
     Reviewer,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+    Status,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Plan {
     pub id: String,
@@ -85,6 +97,12 @@ pub struct Plan {
     pub considerations: Vec<String>,
     pub status: PlanStatus,
     pub approved_at: Option<DateTime<Utc>>,
+    /// A 5- or 6-field cron expression; when set, this plan is a recurring
+    /// template that `CrocEngine::run_scheduled_plans` materializes into a
+    /// fresh concrete plan each time `next_run_at` passes.
+    pub cron_schedule: Option<String>,
+    /// Next time `cron_schedule` fires. Only meaningful alongside `cron_schedule`.
+    pub next_run_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -100,6 +118,8 @@ impl Plan {
             considerations: Vec::new(),
             status: PlanStatus::Pending,
             approved_at: None,
+            cron_schedule: None,
+            next_run_at: None,
             created_at: now,
             updated_at: now,
         }
@@ -122,6 +142,10 @@ pub struct Task {
     pub depends_on: Vec<String>,
     pub worktree: Option<String>,
     pub assigned_worker: Option<String>,
+    /// Number of times this task has been re-spawned after failing.
+    pub retry_count: u32,
+    /// The serialized `CrocError` from the most recent failed attempt, if any.
+    pub last_error: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -140,6 +164,8 @@ impl Task {
             depends_on: Vec::new(),
             worktree: None,
             assigned_worker: None,
+            retry_count: 0,
+            last_error: None,
             created_at: now,
             updated_at: now,
         }
@@ -163,6 +189,8 @@ impl Task {
             depends_on: Vec::new(),
             worktree: None,
             assigned_worker: None,
+            retry_count: 0,
+            last_error: None,
             created_at: now,
             updated_at: now,
         }
@@ -272,6 +300,11 @@ impl Event {
         self
     }
 
+    pub fn with_ts(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
     fn generate_id() -> String {
         format!("evt-{}", Utc::now().timestamp_millis())
     }
@@ -306,3 +339,32 @@ impl Review {
         format!("rev-{}", Utc::now().timestamp_millis())
     }
 }
+
+/// One line of live agent output, persisted straight to the SQLite store so
+/// a plan's transcript survives a tmux session dying or a crash mid-run.
+/// Unlike `Plan`/`Task`/etc. the cache is the source of truth for these —
+/// there is no `logs.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogRecord {
+    pub id: String,
+    pub task_id: String,
+    pub role: Role,
+    pub stream: LogStream,
+    pub seq: u64,
+    pub line: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl LogRecord {
+    pub fn new(task_id: String, role: Role, stream: LogStream, seq: u64, line: String) -> Self {
+        Self {
+            id: format!("log-{}-{}", task_id, seq),
+            task_id,
+            role,
+            stream,
+            seq,
+            line,
+            timestamp: Utc::now(),
+        }
+    }
+}
@@ -0,0 +1,337 @@
+//! Parses the `---CROC_STATUS---`/`---CROC_REVIEW---` trailer blocks that
+//! `prime::build_worker_prompt`, `build_foreman_prompt`, and
+//! `build_reviewer_prompt` ask agents to emit, so the facts, decisions, and
+//! review outcomes they produce stop being discarded console output.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkerStatus {
+    pub subtask: String,
+    pub status: String,
+    pub context_usage: Option<String>,
+    pub files_modified: Vec<String>,
+    pub facts_learned: Vec<FactEntry>,
+    pub decisions_made: Vec<DecisionEntry>,
+    pub work_completed: Option<String>,
+    pub exit_ready: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FactEntry {
+    pub content: String,
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecisionEntry {
+    pub decision: String,
+    pub reasoning: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForemanStatus {
+    pub foreman: String,
+    pub subtasks_total: Option<u32>,
+    pub subtasks_complete: Option<u32>,
+    pub subtasks_running: Vec<String>,
+    pub next_action: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewResult {
+    pub status: String,
+    pub summary: Option<String>,
+    pub issues: Vec<ReviewIssue>,
+    pub recommendations: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewIssue {
+    pub severity: String,
+    pub description: String,
+    pub location: Option<String>,
+}
+
+/// Extracts the text between `---<marker>---` and `---END_<marker>---`.
+fn extract_block<'a>(raw: &'a str, marker: &str) -> Option<&'a str> {
+    let start_tag = format!("---{}---", marker);
+    let end_tag = format!("---END_{}---", marker);
+
+    let start = raw.rfind(&start_tag)? + start_tag.len();
+    let end = raw[start..].find(&end_tag)? + start;
+    Some(raw[start..end].trim())
+}
+
+/// Strips a leading/trailing quote and surrounding whitespace from a value.
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value)
+        .to_string()
+}
+
+fn scalar_field<'a>(lines: &[&'a str], key: &str) -> Option<&'a str> {
+    let prefix = format!("{}:", key);
+    lines
+        .iter()
+        .find(|l| l.trim_start().starts_with(&prefix))
+        .map(|l| l.trim_start().trim_start_matches(&prefix).trim())
+}
+
+/// Parses a trailing `---CROC_STATUS---` worker block out of `raw`.
+pub fn parse_worker_status(raw: &str) -> Option<WorkerStatus> {
+    let block = extract_block(raw, "CROC_STATUS")?;
+    let lines: Vec<&str> = block.lines().collect();
+
+    let subtask = scalar_field(&lines, "SUBTASK")?.to_string();
+    let status = scalar_field(&lines, "STATUS")?.to_string();
+    let context_usage = scalar_field(&lines, "CONTEXT_USAGE").map(str::to_string);
+    let exit_ready = scalar_field(&lines, "EXIT_READY")
+        .map(|v| v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+    let work_completed = scalar_field(&lines, "WORK_COMPLETED").map(unquote);
+
+    let files_modified = scalar_field(&lines, "FILES_MODIFIED")
+        .map(|v| {
+            v.trim_start_matches('[')
+                .trim_end_matches(']')
+                .split(',')
+                .map(|s| s.trim().trim_matches('"').to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let facts_learned = parse_facts(&lines);
+    let decisions_made = parse_decisions(&lines);
+
+    Some(WorkerStatus {
+        subtask,
+        status,
+        context_usage,
+        files_modified,
+        facts_learned,
+        decisions_made,
+        work_completed,
+        exit_ready,
+    })
+}
+
+/// Parses a trailing `---CROC_STATUS---` foreman block out of `raw`.
+pub fn parse_foreman_status(raw: &str) -> Option<ForemanStatus> {
+    let block = extract_block(raw, "CROC_STATUS")?;
+    let lines: Vec<&str> = block.lines().collect();
+
+    let foreman = scalar_field(&lines, "FOREMAN")?.to_string();
+    let subtasks_total = scalar_field(&lines, "SUBTASKS_TOTAL").and_then(|v| v.parse().ok());
+    let subtasks_complete = scalar_field(&lines, "SUBTASKS_COMPLETE").and_then(|v| v.parse().ok());
+    let subtasks_running = scalar_field(&lines, "SUBTASKS_RUNNING")
+        .map(|v| v.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+    let next_action = scalar_field(&lines, "NEXT_ACTION").map(str::to_string);
+
+    Some(ForemanStatus {
+        foreman,
+        subtasks_total,
+        subtasks_complete,
+        subtasks_running,
+        next_action,
+    })
+}
+
+/// Parses a trailing `---CROC_REVIEW---` block out of `raw`.
+pub fn parse_review_result(raw: &str) -> Option<ReviewResult> {
+    let block = extract_block(raw, "CROC_REVIEW")?;
+    let lines: Vec<&str> = block.lines().collect();
+
+    let status = scalar_field(&lines, "STATUS")?.to_string();
+    let summary = scalar_field(&lines, "SUMMARY").map(unquote);
+    let issues = parse_issues(&lines);
+    let recommendations = parse_simple_list(&lines, "RECOMMENDATIONS");
+
+    Some(ReviewResult {
+        status,
+        summary,
+        issues,
+        recommendations,
+    })
+}
+
+fn section_items<'a>(lines: &'a [&'a str], key: &str) -> Vec<&'a str> {
+    let header = format!("{}:", key);
+    let Some(start) = lines.iter().position(|l| l.trim_start() == header) else {
+        return Vec::new();
+    };
+
+    lines[start + 1..]
+        .iter()
+        .take_while(|l| l.trim_start().starts_with('-') || l.starts_with("    "))
+        .copied()
+        .collect()
+}
+
+fn parse_facts(lines: &[&str]) -> Vec<FactEntry> {
+    section_items(lines, "FACTS_LEARNED")
+        .into_iter()
+        .filter(|l| l.trim_start().starts_with('-'))
+        .filter_map(|l| {
+            let item = l.trim_start().trim_start_matches('-').trim();
+            let (content, source) = match item.rsplit_once('(') {
+                Some((content, source)) => (
+                    content.trim(),
+                    Some(source.trim_end_matches(')').trim().to_string()),
+                ),
+                None => (item, None),
+            };
+            if content.is_empty() {
+                None
+            } else {
+                Some(FactEntry {
+                    content: unquote(content),
+                    source,
+                })
+            }
+        })
+        .collect()
+}
+
+fn parse_decisions(lines: &[&str]) -> Vec<DecisionEntry> {
+    let items = section_items(lines, "DECISIONS_MADE");
+    let mut decisions = Vec::new();
+    let mut current_decision: Option<String> = None;
+
+    for line in items {
+        let trimmed = line.trim_start();
+        if let Some(value) = trimmed.strip_prefix("- decision:") {
+            current_decision = Some(unquote(value));
+        } else if let Some(value) = trimmed.strip_prefix("reasoning:") {
+            if let Some(decision) = current_decision.take() {
+                decisions.push(DecisionEntry {
+                    decision,
+                    reasoning: unquote(value),
+                });
+            }
+        }
+    }
+
+    decisions
+}
+
+fn parse_issues(lines: &[&str]) -> Vec<ReviewIssue> {
+    let items = section_items(lines, "ISSUES");
+    let mut issues = Vec::new();
+    let mut severity: Option<String> = None;
+    let mut description: Option<String> = None;
+
+    for line in items {
+        let trimmed = line.trim_start();
+        if let Some(value) = trimmed.strip_prefix("- severity:") {
+            if let (Some(severity), Some(description)) = (severity.take(), description.take()) {
+                issues.push(ReviewIssue {
+                    severity,
+                    description,
+                    location: None,
+                });
+            }
+            severity = Some(value.trim().to_string());
+        } else if let Some(value) = trimmed.strip_prefix("description:") {
+            description = Some(unquote(value));
+        } else if let Some(value) = trimmed.strip_prefix("location:") {
+            if let Some(last) = issues.last_mut() {
+                last.location = Some(unquote(value));
+            } else if let (Some(sev), Some(desc)) = (severity.take(), description.take()) {
+                issues.push(ReviewIssue {
+                    severity: sev,
+                    description: desc,
+                    location: Some(unquote(value)),
+                });
+            }
+        }
+    }
+
+    if let (Some(sev), Some(desc)) = (severity, description) {
+        issues.push(ReviewIssue {
+            severity: sev,
+            description: desc,
+            location: None,
+        });
+    }
+
+    issues
+}
+
+fn parse_simple_list(lines: &[&str], key: &str) -> Vec<String> {
+    section_items(lines, key)
+        .into_iter()
+        .filter(|l| l.trim_start().starts_with('-'))
+        .map(|l| unquote(l.trim_start().trim_start_matches('-').trim()))
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_worker_status_block() {
+        let raw = r#"Some narration here.
+
+---CROC_STATUS---
+SUBTASK: task-abc.1
+STATUS: complete
+CONTEXT_USAGE: 42%
+FILES_MODIFIED: [src/a.rs, src/b.rs]
+FACTS_LEARNED:
+  - "the API returns 404 for missing ids" (src/a.rs)
+DECISIONS_MADE:
+  - decision: "use Option<T> instead of a sentinel"
+    reasoning: "matches the rest of the schema"
+WORK_COMPLETED: "implemented the endpoint"
+EXIT_READY: true
+---END_CROC_STATUS---
+"#;
+
+        let status = parse_worker_status(raw).expect("should parse");
+        assert_eq!(status.subtask, "task-abc.1");
+        assert_eq!(status.status, "complete");
+        assert!(status.exit_ready);
+        assert_eq!(status.files_modified, vec!["src/a.rs", "src/b.rs"]);
+        assert_eq!(status.facts_learned.len(), 1);
+        assert_eq!(status.facts_learned[0].source.as_deref(), Some("src/a.rs"));
+        assert_eq!(status.decisions_made.len(), 1);
+        assert_eq!(status.decisions_made[0].decision, "use Option<T> instead of a sentinel");
+    }
+
+    #[test]
+    fn parses_review_result_block() {
+        let raw = r#"
+---CROC_REVIEW---
+STATUS: changes_requested
+SUMMARY: "needs more tests"
+ISSUES:
+  - severity: major
+    description: "missing error handling"
+    location: "src/foo.rs:10"
+RECOMMENDATIONS:
+  - "add a test for the empty case"
+---END_CROC_REVIEW---
+"#;
+
+        let review = parse_review_result(raw).expect("should parse");
+        assert_eq!(review.status, "changes_requested");
+        assert_eq!(review.summary.as_deref(), Some("needs more tests"));
+        assert_eq!(review.issues.len(), 1);
+        assert_eq!(review.issues[0].severity, "major");
+        assert_eq!(review.issues[0].location.as_deref(), Some("src/foo.rs:10"));
+        assert_eq!(review.recommendations, vec!["add a test for the empty case"]);
+    }
+
+    #[test]
+    fn returns_none_without_a_block() {
+        assert!(parse_worker_status("no status block here").is_none());
+    }
+}
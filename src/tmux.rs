@@ -15,21 +15,37 @@ impl TmuxSession {
         &self.name
     }
 
-    pub fn spawn(&self, command: &str) -> Result<()> {
-        debug!(session = %self.name, command = %command, "Spawning tmux session");
+    /// Spawns `program` (with `args`) as the session's command, exporting
+    /// `env` into that session only (via tmux's per-session `-e`). Unlike
+    /// the old `"VAR=val {} prog"` string this replaced, `program`/`args`
+    /// are handed to `tmux` as separate argv entries and `tmux` execs them
+    /// directly — nothing here is ever parsed by a shell, so untrusted
+    /// values (e.g. ids that reached us over the RPC socket) can't break
+    /// out of the command.
+    pub fn spawn(&self, program: &str, args: &[&str], env: &[(&str, &str)]) -> Result<()> {
+        debug!(session = %self.name, program = %program, ?args, "Spawning tmux session");
+
+        let mut tmux_args: Vec<&str> = vec!["new-session", "-d", "-s", &self.name];
+        let env_pairs: Vec<String> = env.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        for pair in &env_pairs {
+            tmux_args.push("-e");
+            tmux_args.push(pair);
+        }
+        tmux_args.push(program);
+        tmux_args.extend_from_slice(args);
 
         let output = Command::new("tmux")
-            .args(["new-session", "-d", "-s", &self.name, command])
+            .args(&tmux_args)
             .output()
-            .map_err(|e| CrocError::Tmux {
-                message: format!("Failed to spawn session '{}': {}", self.name, e),
-            })?;
+            .map_err(|e| CrocError::tmux("new-session", &self.name, None, e.to_string()))?;
 
         if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(CrocError::Tmux {
-                message: format!("tmux new-session failed: {}", stderr),
-            });
+            return Err(CrocError::tmux(
+                "new-session",
+                &self.name,
+                output.status.code(),
+                String::from_utf8_lossy(&output.stderr),
+            ));
         }
 
         info!(session = %self.name, "Spawned tmux session");
@@ -41,9 +57,7 @@ impl TmuxSession {
             .args(["has-session", "-t", &self.name])
             .stderr(std::process::Stdio::null())
             .status()
-            .map_err(|e| CrocError::Tmux {
-                message: format!("Failed to check session '{}': {}", self.name, e),
-            })?;
+            .map_err(|e| CrocError::tmux("has-session", &self.name, None, e.to_string()))?;
 
         Ok(status.success())
     }
@@ -54,15 +68,15 @@ impl TmuxSession {
         let output = Command::new("tmux")
             .args(["send-keys", "-t", &self.name, keys, "Enter"])
             .output()
-            .map_err(|e| CrocError::Tmux {
-                message: format!("Failed to send keys to '{}': {}", self.name, e),
-            })?;
+            .map_err(|e| CrocError::tmux("send-keys", &self.name, None, e.to_string()))?;
 
         if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(CrocError::Tmux {
-                message: format!("tmux send-keys failed: {}", stderr),
-            });
+            return Err(CrocError::tmux(
+                "send-keys",
+                &self.name,
+                output.status.code(),
+                String::from_utf8_lossy(&output.stderr),
+            ));
         }
 
         Ok(())
@@ -72,15 +86,15 @@ impl TmuxSession {
         let output = Command::new("tmux")
             .args(["capture-pane", "-t", &self.name, "-p"])
             .output()
-            .map_err(|e| CrocError::Tmux {
-                message: format!("Failed to capture pane '{}': {}", self.name, e),
-            })?;
+            .map_err(|e| CrocError::tmux("capture-pane", &self.name, None, e.to_string()))?;
 
         if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(CrocError::Tmux {
-                message: format!("tmux capture-pane failed: {}", stderr),
-            });
+            return Err(CrocError::tmux(
+                "capture-pane",
+                &self.name,
+                output.status.code(),
+                String::from_utf8_lossy(&output.stderr),
+            ));
         }
 
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
@@ -92,15 +106,15 @@ impl TmuxSession {
         let output = Command::new("tmux")
             .args(["kill-session", "-t", &self.name])
             .output()
-            .map_err(|e| CrocError::Tmux {
-                message: format!("Failed to kill session '{}': {}", self.name, e),
-            })?;
+            .map_err(|e| CrocError::tmux("kill-session", &self.name, None, e.to_string()))?;
 
         if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(CrocError::Tmux {
-                message: format!("tmux kill-session failed: {}", stderr),
-            });
+            return Err(CrocError::tmux(
+                "kill-session",
+                &self.name,
+                output.status.code(),
+                String::from_utf8_lossy(&output.stderr),
+            ));
         }
 
         info!(session = %self.name, "Killed tmux session");
@@ -113,14 +127,10 @@ impl TmuxSession {
         let status = Command::new("tmux")
             .args(["attach-session", "-t", &self.name])
             .status()
-            .map_err(|e| CrocError::Tmux {
-                message: format!("Failed to attach to '{}': {}", self.name, e),
-            })?;
+            .map_err(|e| CrocError::tmux("attach-session", &self.name, None, e.to_string()))?;
 
         if !status.success() {
-            return Err(CrocError::Tmux {
-                message: format!("tmux attach-session failed for '{}'", self.name),
-            });
+            return Err(CrocError::tmux("attach-session", &self.name, status.code(), ""));
         }
 
         Ok(())
@@ -131,9 +141,7 @@ pub fn list_sessions() -> Result<Vec<String>> {
     let output = Command::new("tmux")
         .args(["list-sessions", "-F", "#{session_name}"])
         .output()
-        .map_err(|e| CrocError::Tmux {
-            message: format!("Failed to list sessions: {}", e),
-        })?;
+        .map_err(|e| CrocError::tmux("list-sessions", "-", None, e.to_string()))?;
 
     if !output.status.success() {
         return Ok(Vec::new());